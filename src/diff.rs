@@ -0,0 +1,75 @@
+/*
+ * MIT license
+ *
+ * Copyright (c) 2023 Dariusz Depta
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Structural differences between `HTML` element trees.
+
+use alloc::string::String;
+use core::fmt;
+
+/// Kind of the structural difference between two elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DifferenceKind {
+  /// Element names differ.
+  NameChanged { old: String, new: String },
+  /// Attribute is present only in the other element.
+  AttributeAdded { name: String, value: String },
+  /// Attribute is present only in this element.
+  AttributeRemoved { name: String },
+  /// Attribute values differ.
+  AttributeChanged { name: String, old: String, new: String },
+  /// Contents differ.
+  ContentChanged,
+  /// Numbers of children differ.
+  ChildCountChanged { old: usize, new: usize },
+}
+
+/// Structural difference between two elements, reported by [HtmlElement::diff](crate::HtmlElement::diff).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Difference {
+  path: String,
+  kind: DifferenceKind,
+}
+
+impl Difference {
+  /// Creates a new difference found at specified path.
+  pub(crate) fn new(path: &str, kind: DifferenceKind) -> Self {
+    Self { path: path.into(), kind }
+  }
+
+  /// Returns the path to the differing element, like `body/div[0]/p[1]`,
+  /// where the numbers are positions of the elements among their siblings.
+  pub fn path(&self) -> &str {
+    &self.path
+  }
+
+  /// Returns the kind of the difference.
+  pub fn kind(&self) -> &DifferenceKind {
+    &self.kind
+  }
+}
+
+impl fmt::Display for Difference {
+  /// Converts the difference into text.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}: ", self.path)?;
+    match &self.kind {
+      DifferenceKind::NameChanged { old, new } => write!(f, "name changed from '{}' to '{}'", old, new),
+      DifferenceKind::AttributeAdded { name, value } => write!(f, "attribute '{}' added with value '{}'", name, value),
+      DifferenceKind::AttributeRemoved { name } => write!(f, "attribute '{}' removed", name),
+      DifferenceKind::AttributeChanged { name, old, new } => write!(f, "attribute '{}' changed from '{}' to '{}'", name, old, new),
+      DifferenceKind::ContentChanged => write!(f, "content changed"),
+      DifferenceKind::ChildCountChanged { old, new } => write!(f, "number of children changed from {} to {}", old, new),
+    }
+  }
+}
@@ -0,0 +1,119 @@
+/*
+ * MIT license
+ *
+ * Copyright (c) 2023 Dariusz Depta
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Escaping of text content and attribute values.
+
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use core::fmt;
+
+/// Escapes special characters (`&`, `<` and `>`) in `HTML` text content,
+/// using the same rules as helpers setting escaped content, like [HtmlElement::with_text](crate::HtmlElement::with_text).
+///
+/// When there are no special characters, the input is returned without allocation.
+pub fn escape_text(input: &str) -> Cow<'_, str> {
+  if !input.contains(['&', '<', '>']) {
+    return Cow::Borrowed(input);
+  }
+  let mut output = String::with_capacity(input.len());
+  for ch in input.chars() {
+    match ch {
+      '&' => output.push_str("&amp;"),
+      '<' => output.push_str("&lt;"),
+      '>' => output.push_str("&gt;"),
+      _ => output.push(ch),
+    }
+  }
+  Cow::Owned(output)
+}
+
+/// Escapes special characters (`&`, `<`, `>`, `"` and `'`) in `HTML` attribute values,
+/// using the same rules as helpers setting escaped attributes, like [HtmlElement::new_meta](crate::HtmlElement::new_meta).
+///
+/// When there are no special characters, the input is returned without allocation.
+pub fn escape_attr(input: &str) -> Cow<'_, str> {
+  if !input.contains(['&', '<', '>', '"', '\'']) {
+    return Cow::Borrowed(input);
+  }
+  let mut output = String::with_capacity(input.len());
+  for ch in input.chars() {
+    match ch {
+      '&' => output.push_str("&amp;"),
+      '<' => output.push_str("&lt;"),
+      '>' => output.push_str("&gt;"),
+      '"' => output.push_str("&quot;"),
+      '\'' => output.push_str("&#39;"),
+      _ => output.push(ch),
+    }
+  }
+  Cow::Owned(output)
+}
+
+/// Replaces named character references of escaped special characters
+/// with numeric character references, e.g. `&amp;` with `&#38;`.
+pub(crate) fn numeric_references(input: &str) -> Cow<'_, str> {
+  if !input.contains('&') {
+    return Cow::Borrowed(input);
+  }
+  let mut output = String::with_capacity(input.len());
+  let mut rest = input;
+  while let Some(start) = rest.find('&') {
+    output.push_str(&rest[..start]);
+    rest = &rest[start..];
+    match [("&amp;", "&#38;"), ("&lt;", "&#60;"), ("&gt;", "&#62;"), ("&quot;", "&#34;")]
+      .iter()
+      .find(|(named, _)| rest.starts_with(named))
+    {
+      Some((named, numeric)) => {
+        output.push_str(numeric);
+        rest = &rest[named.len()..];
+      }
+      None => {
+        output.push('&');
+        rest = &rest[1..];
+      }
+    }
+  }
+  output.push_str(rest);
+  Cow::Owned(output)
+}
+
+/// Writes the text with all non-ASCII characters replaced with numeric character references.
+pub(crate) fn write_ascii_only<W: fmt::Write>(w: &mut W, input: &str) -> fmt::Result {
+  for ch in input.chars() {
+    if ch.is_ascii() {
+      w.write_char(ch)?;
+    } else {
+      write!(w, "&#{};", ch as u32)?;
+    }
+  }
+  Ok(())
+}
+
+/// Percent-encodes the URL, preserving its structure.
+///
+/// Unreserved and reserved URL characters, and already present percent signs are preserved,
+/// all other characters (like spaces and non-ASCII characters) are percent-encoded as `UTF-8` bytes.
+pub(crate) fn percent_encode_url(url: &str) -> String {
+  let mut output = String::with_capacity(url.len());
+  for byte in url.bytes() {
+    if byte.is_ascii_alphanumeric() || b"-._~:/?#[]@!$&'()*+,;=%".contains(&byte) {
+      output.push(byte as char);
+    } else {
+      output.push_str(&format!("%{:02X}", byte));
+    }
+  }
+  output
+}
@@ -0,0 +1,79 @@
+/*
+ * MIT license
+ *
+ * Copyright (c) 2023 Dariusz Depta
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Builder of complete `HTML` forms.
+
+use crate::escape::{escape_attr, escape_text};
+use crate::html_builder::HtmlElement;
+
+/// Builder assembling a `<form>` element with labeled inputs and a submit button.
+pub struct FormBuilder {
+  form: HtmlElement,
+}
+
+impl FormBuilder {
+  /// Creates a new form builder with specified `method` and `action` attributes.
+  pub fn new(method: &str, action: &str) -> Self {
+    let mut form = HtmlElement::new("form");
+    form.set_attr("method", escape_attr(method));
+    form.set_attr("action", escape_attr(action));
+    Self { form }
+  }
+
+  /// Adds a labeled text input field.
+  pub fn text_field(mut self, name: &str, label: &str) -> Self {
+    self.form.add_child(HtmlElement::new_labeled_input(name, label, "text", name));
+    self
+  }
+
+  /// Adds a labeled checkbox, the label follows the checkbox.
+  pub fn checkbox(mut self, name: &str, label: &str) -> Self {
+    let mut field = HtmlElement::new_div(None);
+    field.add_child(new_input("checkbox", name));
+    field.add_child(new_label(name, label));
+    self.form.add_child(field);
+    self
+  }
+
+  /// Adds a submit button with specified text.
+  pub fn submit(mut self, text: &str) -> Self {
+    let mut button = HtmlElement::new("button");
+    button.set_attr("type", "submit");
+    button.set_content(&escape_text(text));
+    self.form.add_child(button);
+    self
+  }
+
+  /// Returns the assembled `<form>` element.
+  pub fn build(self) -> HtmlElement {
+    self.form
+  }
+}
+
+/// Creates a `<label>` element for the input with specified name.
+fn new_label(name: &str, label: &str) -> HtmlElement {
+  let mut element = HtmlElement::new("label");
+  element.set_attr("for", escape_attr(name));
+  element.set_content(&escape_text(label));
+  element
+}
+
+/// Creates an `<input>` element of specified type, identified by its name.
+fn new_input(input_type: &str, name: &str) -> HtmlElement {
+  let mut element = HtmlElement::new_void("input");
+  element.set_attr("type", input_type);
+  element.set_attr("id", escape_attr(name));
+  element.set_attr("name", escape_attr(name));
+  element
+}
@@ -12,11 +12,35 @@
  * SOFTWARE.
  */
 
-use std::fmt;
-use std::fmt::Write;
+use crate::diff::{Difference, DifferenceKind};
+use crate::escape::{escape_attr, escape_text, numeric_references, percent_encode_url, write_ascii_only};
+use crate::options::{EntityStyle, SerializeOptions};
+use crate::validation::{BuildError, Severity, ValidationIssue};
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
 
 /// Common indentation value.
-const INDENT: usize = 2;
+pub(crate) const INDENT: usize = 2;
+
+/// ANSI color of tag names in highlighted output.
+const COLOR_TAG: &str = "\x1b[34m";
+
+/// ANSI color of attribute names in highlighted output.
+const COLOR_ATTR_NAME: &str = "\x1b[36m";
+
+/// ANSI color of attribute values in highlighted output.
+const COLOR_ATTR_VALUE: &str = "\x1b[32m";
+
+/// ANSI color of text content in highlighted output.
+const COLOR_TEXT: &str = "\x1b[33m";
+
+/// ANSI code resetting the color in highlighted output.
+const COLOR_RESET: &str = "\x1b[0m";
 
 /// Reference of the `HTML` standard.
 const HREF_XMLNS: &str = "http://www.w3.org/1999/xhtml";
@@ -37,18 +61,149 @@ pub enum HeadingLevel {
   H3,
 }
 
+/// Definition of `HTML` text directions.
+pub enum TextDirection {
+  /// Left-to-right text direction.
+  Ltr,
+  /// Right-to-left text direction.
+  Rtl,
+  /// Text direction determined by the user agent.
+  Auto,
+}
+
+impl TextDirection {
+  /// Returns the value of the `dir` attribute for this direction.
+  fn as_str(&self) -> &'static str {
+    match self {
+      TextDirection::Ltr => "ltr",
+      TextDirection::Rtl => "rtl",
+      TextDirection::Auto => "auto",
+    }
+  }
+}
+
+/// Definition of `HTML` media element kinds.
+pub enum MediaKind {
+  /// Media element `<audio>`.
+  Audio,
+  /// Media element `<video>`.
+  Video,
+}
+
+impl MediaKind {
+  /// Returns the tag name of the media element.
+  fn as_str(&self) -> &'static str {
+    match self {
+      MediaKind::Audio => "audio",
+      MediaKind::Video => "video",
+    }
+  }
+}
+
+/// Definition of a table column, serialized as `<col>` element within `<colgroup>`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ColSpec<'a> {
+  /// Width of the column, like `30%` or `12em`, serialized as `style` attribute.
+  pub width: Option<&'a str>,
+  /// Class names of the column.
+  pub class: Option<&'a str>,
+}
+
+impl<'a> ColSpec<'a> {
+  /// Creates a new column definition with specified width.
+  pub fn width(width: &'a str) -> Self {
+    Self { width: Some(width), class: None }
+  }
+
+  /// Returns the column definition with specified class names.
+  pub fn with_class(self, class: &'a str) -> Self {
+    Self { class: Some(class), ..self }
+  }
+}
+
 /// Structure representing whole `HTML` document.
 #[derive(Debug, Clone)]
 pub struct HtmlDocument {
   root: HtmlElement,
+  doctype: bool,
 }
 
 impl HtmlDocument {
+  /// Creates a new `HTML` document with default header and specified body.
   ///
+  /// When `lang` is not a plausible `BCP 47` language tag,
+  /// the `lang` attribute is omitted from the document root.
   pub fn new(title: &str, lang: &str, styles: &[&str], body: HtmlElement) -> Self {
-    let mut root = HtmlElement::new("html");
-    root.set_attr("lang", lang);
-    root.set_attr("xmlns", HREF_XMLNS);
+    Self::new_with_stylesheet(title, lang, &styles.join("\n"), body)
+  }
+
+  /// Creates a new `HTML` document like [HtmlDocument::new], validating the input first.
+  ///
+  /// Returns an error aggregating all found issues, when `lang` is not a plausible
+  /// `BCP 47` language tag, when the body contains elements with invalid names,
+  /// elements with duplicated `id` attributes, or other validation errors.
+  pub fn try_new(title: &str, lang: &str, styles: &[&str], body: HtmlElement) -> Result<Self, BuildError> {
+    let mut issues = vec![];
+    if !is_valid_lang(lang) {
+      issues.push(ValidationIssue::error("html", &format!("has invalid lang '{}'", lang)));
+    }
+    let mut ids = vec![];
+    body.walk(&mut |element| {
+      if element.text {
+        return;
+      }
+      if !is_valid_name(&element.name) {
+        issues.push(ValidationIssue::error(&element.name, "has invalid name"));
+      }
+      for attribute in element.attributes.iter().filter(|attribute| attribute.name == "id") {
+        let id = attribute.value();
+        if ids.iter().any(|known: &String| *known == id) {
+          issues.push(ValidationIssue::error(&element.name, &format!("has duplicated id '{}'", id)));
+        } else {
+          ids.push(id.into_owned());
+        }
+      }
+    });
+    issues.extend(body.validate().into_iter().filter(|issue| issue.severity() == Severity::Error));
+    if issues.is_empty() {
+      Ok(Self::new(title, lang, styles, body))
+    } else {
+      Err(BuildError::new(issues))
+    }
+  }
+
+  /// Creates a new `HTML` document with default header and body containing specified sections.
+  pub fn new_from_sections(title: &str, lang: &str, styles: &[&str], sections: Vec<HtmlElement>) -> Self {
+    let mut body = HtmlElement::new("body");
+    body.add_children(sections);
+    Self::new(title, lang, styles, body)
+  }
+
+  /// Creates a new `HTML` document with default header and body, where each style
+  /// is placed in a separate `<style>` element, with `media` attribute when the media query is specified.
+  /// Empty styles are skipped.
+  ///
+  /// Invalid `lang` is handled the same way as in [HtmlDocument::new].
+  pub fn new_with_media_styles(title: &str, lang: &str, styles: &[(Option<&str>, &str)], body: HtmlElement) -> Self {
+    let mut document = Self::new_with_stylesheet(title, lang, "", body);
+    if let Some(head) = document.head_mut() {
+      for (media, css) in styles.iter().filter(|(_, css)| !css.is_empty()) {
+        let mut style = HtmlElement::new("style").with_raw(css);
+        style.set_attr_opt("media", media.map(escape_attr));
+        head.add_child(style);
+      }
+    }
+    document
+  }
+
+  /// Creates a new `HTML` document with default header, specified stylesheet and body.
+  ///
+  /// The stylesheet is used as is, so joining multiple styles
+  /// may be done once, when generating many documents.
+  /// When the stylesheet is empty, no `<style>` element is added.
+  /// Invalid `lang` is handled the same way as in [HtmlDocument::new].
+  pub fn new_with_stylesheet(title: &str, lang: &str, stylesheet: &str, body: HtmlElement) -> Self {
+    let mut root = new_root(lang);
     // prepare HTML header
     let mut head = HtmlElement::new("head");
     // <meta>
@@ -74,41 +229,492 @@ impl HtmlDocument {
     link.set_attr("rel", "stylesheet");
     link.set_attr("href", HREF_FONT_MONO);
     head.add_child(link);
-    // <style>
-    let mut style = HtmlElement::new("style");
-    style.set_content(&styles.join("\n"));
-    head.add_child(style);
+    // <style> (omitted when there are no styles)
+    if !stylesheet.is_empty() {
+      let mut style = HtmlElement::new("style");
+      style.set_content(stylesheet);
+      head.add_child(style);
+    }
     // finalize header
     root.add_child(head);
     // add HTML document body
     root.add_child(body);
-    Self { root }
+    Self { root, doctype: true }
+  }
+
+  /// Creates a new document with specified root element, used as is.
+  ///
+  /// When `doctype` is `false`, no `<!DOCTYPE html>` line is written, so the document
+  /// may be used for `XML` output with arbitrary root element, where namespace
+  /// declarations are set as attributes of the root element.
+  pub fn from_root(root: HtmlElement, doctype: bool) -> Self {
+    Self { root, doctype }
+  }
+
+  /// Creates a new `HTML` document with specified header and body.
+  ///
+  /// The header is used as is, no default header elements are added.
+  /// Invalid `lang` is handled the same way as in [HtmlDocument::new].
+  pub fn with_head_body(lang: &str, head: HtmlElement, body: HtmlElement) -> Self {
+    let mut root = new_root(lang);
+    root.add_child(head);
+    root.add_child(body);
+    Self { root, doctype: true }
+  }
+
+  /// Arranges the children of the document header in the recommended order:
+  /// `<meta charset>` first, then `<title>`, other `<meta>` elements, `<link>` elements,
+  /// `<style>` elements and all remaining elements, keeping the relative order
+  /// of elements within each group.
+  pub fn sort_head(&mut self) {
+    if let Some(head) = self.head_mut() {
+      head.children_mut().sort_by_key(head_rank);
+    }
+  }
+
+  /// Appends CSS to the style block without `media` attribute in the document header.
+  ///
+  /// When the header contains no such style block, a new one is added.
+  /// Empty CSS is ignored.
+  pub fn add_css(&mut self, css: &str) {
+    if css.is_empty() {
+      return;
+    }
+    if let Some(head) = self.head_mut() {
+      if let Some(style) = head.children_mut().iter_mut().find(|child| child.name == "style" && !child.has_attr("media")) {
+        if style.content.as_ref().is_some_and(|content| !content.is_empty()) {
+          style.append_content("\n");
+        }
+        style.append_content(css);
+      } else {
+        let mut style = HtmlElement::new("style");
+        style.set_content(css);
+        head.add_child(style);
+      }
+    }
+  }
+
+  /// Adds a resource hint `<link>` element (like `preload`, `prefetch`, `preconnect`
+  /// or `dns-prefetch`) to the document header, with optional `as` attribute.
+  ///
+  /// Fonts are always fetched in anonymous mode, so preloaded fonts get `crossorigin` attribute.
+  pub fn add_resource_hint(&mut self, rel: &str, href: &str, as_type: Option<&str>) {
+    if let Some(head) = self.head_mut() {
+      let mut link = HtmlElement::new_void("link").with_attr("rel", escape_attr(rel));
+      link.set_url_attr("href", href);
+      link.set_attr_opt("as", as_type.map(escape_attr));
+      if as_type == Some("font") {
+        link.set_bool_attr("crossorigin");
+      }
+      head.add_child(link);
+    }
+  }
+
+  /// Adds a viewport `<meta>` element to the document header, after `<meta charset>`,
+  /// `<title>` and all other `<meta>` elements, in the order arranged by [HtmlDocument::sort_head].
+  ///
+  /// For responsive documents the typical content is `width=device-width, initial-scale=1`.
+  pub fn add_viewport(&mut self, content: &str) {
+    if let Some(head) = self.head_mut() {
+      let viewport = HtmlElement::new_meta("viewport", content);
+      let rank = head_rank(&viewport);
+      let index = head.children.iter().rposition(|child| head_rank(child) <= rank).map_or(0, |index| index + 1);
+      head.insert_child(index, viewport);
+    }
+  }
+
+  /// Returns a mutable reference to the document header, if present.
+  fn head_mut(&mut self) -> Option<&mut HtmlElement> {
+    self.root.children_mut().iter_mut().find(|child| child.name == "head")
+  }
+
+  /// Returns a mutable reference to the document body, if present.
+  pub fn body_mut(&mut self) -> Option<&mut HtmlElement> {
+    self.root.children_mut().iter_mut().find(|child| child.name == "body")
+  }
+
+  /// Returns a mutable reference to the root element of the document.
+  pub fn root_mut(&mut self) -> &mut HtmlElement {
+    self.root.invalidate();
+    &mut self.root
+  }
+
+  /// Sets an attribute of the root `<html>` element with escaped value, like `class`
+  /// or `prefix`, replacing the existing attribute with the same name.
+  pub fn set_root_attr(&mut self, name: &str, value: &str) {
+    self.root.replace_attr(name, Some(AttributeValue::Str(escape_attr(value).into_owned())));
+  }
+
+  /// Sets the text direction (`dir` attribute) of the document.
+  pub fn set_dir(&mut self, dir: TextDirection) {
+    self.root.replace_attr("dir", Some(AttributeValue::Str(dir.as_str().to_string())));
+  }
+
+  /// Serializes the document into the specified writer.
+  ///
+  /// Writing stops at the first error, which is returned to the caller.
+  #[cfg(feature = "std")]
+  pub fn write_to<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+    self.write_to_with(writer, &SerializeOptions::default())
+  }
+
+  /// Serializes the indented document into the specified writer, like [HtmlDocument::write_to].
+  #[cfg(feature = "std")]
+  pub fn to_writer_pretty<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+    self.write_to(writer)
+  }
+
+  /// Serializes the minified document into the specified writer.
+  ///
+  /// Writing stops at the first error, which is returned to the caller.
+  #[cfg(feature = "std")]
+  pub fn to_writer_minified<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+    self.write_to_with(writer, &SerializeOptions { minify: true, ..Default::default() })
+  }
+
+  /// Serializes the document into the specified writer using specified options.
+  #[cfg(feature = "std")]
+  fn write_to_with<W: io::Write>(&self, writer: &mut W, opts: &SerializeOptions) -> io::Result<()> {
+    let mut adapter = IoAdapter::new(writer);
+    self.write_document(0, opts, &mut adapter).map_err(|_| adapter.into_error())
+  }
+
+  /// Serializes the document using specified options, including the doctype line.
+  pub fn write_with(&self, opts: &SerializeOptions, buffer: &mut String) {
+    let _ = self.write_document(0, opts, buffer);
+  }
+
+  /// Returns the hash of the serialized document, computed while serializing, without building the serialized text.
+  ///
+  /// The hash is calculated with 64-bit `FNV-1a` algorithm, so it is stable across
+  /// builds and platforms and may be used e.g. for generating `ETag` headers.
+  pub fn content_hash(&self) -> u64 {
+    let mut writer = HashingWriter::default();
+    let _ = self.write_document(0, &SerializeOptions::default(), &mut writer);
+    writer.0
+  }
+
+  /// Serializes the document into `UTF-8` bytes.
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut writer = BytesWriter::default();
+    let _ = self.write_document(0, &SerializeOptions::default(), &mut writer);
+    writer.0
+  }
+
+  /// Serializes the document into bytes in specified encoding, like `windows-1252`.
+  ///
+  /// The `charset` of the `<meta>` element in the document header is set to the name
+  /// of the encoding, characters not representable in the encoding are serialized
+  /// as numeric character references, e.g. `ż` as `&#380;` in `windows-1252`.
+  /// Within `<script>` and `<style>` elements, where references are not decoded,
+  /// such characters are serialized as `JavaScript` or `CSS` escapes, e.g. `\u017C` or `\17C `.
+  /// Encodings not usable for output (`UTF-16LE`, `UTF-16BE` and `replacement`)
+  /// are replaced with `UTF-8`, both in the `charset` and in the output bytes.
+  #[cfg(feature = "encoding")]
+  pub fn to_encoded_bytes(&self, encoding: &'static encoding_rs::Encoding) -> Vec<u8> {
+    // encodings like UTF-16 can not be used for output, they are replaced with UTF-8
+    let encoding = encoding.output_encoding();
+    let mut document = self.clone();
+    if let Some(head) = document.head_mut() {
+      if let Some(meta) = head.children_mut().iter_mut().find(|child| child.name == "meta" && child.has_attr("charset")) {
+        meta.replace_attr("charset", Some(AttributeValue::Str(encoding.name().to_string())));
+      }
+    }
+    document.root.walk_mut(&mut |element| {
+      if is_raw_text(&element.name) {
+        let css = element.name == "style";
+        if let Some(content) = &mut element.content {
+          *content = escape_unencodable(content, encoding, css).into_owned();
+        }
+        for child in element.children.iter_mut().filter(|child| child.text) {
+          if let Some(content) = &mut child.content {
+            *content = escape_unencodable(content, encoding, css).into_owned();
+          }
+        }
+      }
+    });
+    let text = document.to_string();
+    let (bytes, _, _) = encoding.encode(&text);
+    bytes.into_owned()
+  }
+
+  /// Serializes the document with all lines offset by the specified base indentation.
+  pub fn write_at(&self, base_indent: usize, buffer: &mut String) {
+    let _ = self.write_document(base_indent, &SerializeOptions::default(), buffer);
+  }
+
+  /// Serializes the document, including the doctype declaration.
+  fn write_document<W: fmt::Write>(&self, indent: usize, opts: &SerializeOptions, w: &mut W) -> fmt::Result {
+    if self.doctype {
+      write!(w, "{}<!DOCTYPE html>{}", opts.indentation(indent), opts.line_break())?;
+    }
+    self.root.write_element(indent, true, Context::new(opts), w)
+  }
+}
+
+/// Serializes the optional element, returns an empty string when there is no element.
+pub fn render_opt(opt: &Option<HtmlElement>) -> String {
+  opt.as_ref().map(|element| element.to_string()).unwrap_or_default()
+}
+
+/// Returns the rank of the element in the recommended order of header elements.
+fn head_rank(element: &HtmlElement) -> usize {
+  match element.name.as_str() {
+    "meta" if element.attributes.iter().any(|attribute| attribute.name == "charset") => 0,
+    "title" => 1,
+    "meta" => 2,
+    "link" => 3,
+    "style" => 4,
+    _ => 5,
+  }
+}
+
+/// Creates the root element of the `HTML` document.
+fn new_root(lang: &str) -> HtmlElement {
+  let mut root = HtmlElement::new("html");
+  if is_valid_lang(lang) {
+    root.set_attr("lang", lang);
   }
+  root.set_attr("xmlns", HREF_XMLNS);
+  root
+}
+
+/// Returns `true` when the specified text is a valid element name, i.e. starts with
+/// an ASCII letter and contains only ASCII alphanumeric characters and hyphens.
+fn is_valid_name(name: &str) -> bool {
+  name.starts_with(|ch: char| ch.is_ascii_alphabetic()) && name.chars().all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+}
+
+/// Returns `true` when the specified text is a plausible `BCP 47` language tag,
+/// i.e. non-empty, alphabetic primary subtag and alphanumeric subtags,
+/// each of them having at most 8 characters, separated with hyphens.
+fn is_valid_lang(lang: &str) -> bool {
+  let mut subtags = lang.split('-');
+  let primary_valid = subtags.next().is_some_and(|subtag| (1..=8).contains(&subtag.len()) && subtag.chars().all(|ch| ch.is_ascii_alphabetic()));
+  primary_valid && subtags.all(|subtag| (1..=8).contains(&subtag.len()) && subtag.chars().all(|ch| ch.is_ascii_alphanumeric()))
 }
 
 impl fmt::Display for HtmlDocument {
   /// Converts `HTML` document into text.
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     let mut buffer = String::new();
-    let _ = writeln!(&mut buffer, "<!DOCTYPE html>");
-    self.root.write(0, &mut buffer);
+    let _ = self.write_document(0, &SerializeOptions::default(), &mut buffer);
     write!(f, "{}", buffer)
   }
 }
 
-#[derive(Debug, Clone)]
-struct HtmlAttribute {
+/// Value of an `HTML` attribute, formatted when the element is serialized.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum AttributeValue {
+  /// Textual value.
+  Str(String),
+  /// Integer value.
+  Int(i64),
+  /// Boolean attribute, serialized without value.
+  Bool,
+}
+
+/// Writer collecting formatted text as `UTF-8` bytes.
+#[derive(Default)]
+struct BytesWriter(Vec<u8>);
+
+impl fmt::Write for BytesWriter {
+  /// Appends the bytes of the text.
+  fn write_str(&mut self, s: &str) -> fmt::Result {
+    self.0.extend_from_slice(s.as_bytes());
+    Ok(())
+  }
+}
+
+/// Writer hashing the bytes of formatted text with 64-bit `FNV-1a` algorithm, without storing it.
+struct HashingWriter(u64);
+
+impl Default for HashingWriter {
+  /// Creates a writer with the `FNV-1a` offset basis.
+  fn default() -> Self {
+    Self(0xcbf2_9ce4_8422_2325)
+  }
+}
+
+impl fmt::Write for HashingWriter {
+  /// Mixes the bytes of the text into the hash.
+  fn write_str(&mut self, s: &str) -> fmt::Result {
+    for byte in s.bytes() {
+      self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+    }
+    Ok(())
+  }
+}
+
+/// Iterator over descendants of an element in document order.
+struct Descendants<'a> {
+  /// Elements remaining to be visited, the next one on top.
+  stack: Vec<&'a HtmlElement>,
+}
+
+impl<'a> Iterator for Descendants<'a> {
+  type Item = &'a HtmlElement;
+
+  /// Returns the next descendant, placing its children on top of the remaining elements.
+  fn next(&mut self) -> Option<Self::Item> {
+    let element = self.stack.pop()?;
+    self.stack.extend(element.children.iter().rev());
+    Some(element)
+  }
+}
+
+/// Writer counting the bytes of formatted text, without storing it.
+#[derive(Default)]
+struct CountingWriter(usize);
+
+impl fmt::Write for CountingWriter {
+  /// Adds the length of the text in bytes.
+  fn write_str(&mut self, s: &str) -> fmt::Result {
+    self.0 += s.len();
+    Ok(())
+  }
+}
+
+/// Adapter writing formatted text into [io::Write], that keeps the first reported error.
+#[cfg(feature = "std")]
+struct IoAdapter<'a, W: io::Write> {
+  writer: &'a mut W,
+  error: Option<io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, W: io::Write> IoAdapter<'a, W> {
+  /// Creates a new adapter for specified writer.
+  fn new(writer: &'a mut W) -> Self {
+    Self { writer, error: None }
+  }
+
+  /// Returns the error reported by the writer.
+  fn into_error(self) -> io::Error {
+    self.error.unwrap_or_else(|| io::Error::other("formatting error"))
+  }
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> fmt::Write for IoAdapter<'_, W> {
+  /// Writes the text into underlying writer, keeping the error if any.
+  fn write_str(&mut self, s: &str) -> fmt::Result {
+    self.writer.write_all(s.as_bytes()).map_err(|e| {
+      self.error = Some(e);
+      fmt::Error
+    })
+  }
+}
+
+/// Cache of the serialized output of an element.
+///
+/// The cache is ignored when comparing elements.
+#[derive(Debug, Default)]
+struct SerializationCache {
+  /// Indentation and the serialized output of the element.
+  #[cfg(feature = "std")]
+  output: std::sync::Mutex<Option<(usize, std::sync::Arc<str>)>>,
+}
+
+impl SerializationCache {
+  /// Returns the cached output, when cached for specified indentation.
+  #[cfg(feature = "std")]
+  fn get(&self, indent: usize) -> Option<std::sync::Arc<str>> {
+    match self.lock().as_ref() {
+      Some((cached_indent, output)) if *cached_indent == indent => Some(output.clone()),
+      _ => None,
+    }
+  }
+
+  /// Returns the cached output, when cached for specified indentation.
+  #[cfg(not(feature = "std"))]
+  fn get(&self, _indent: usize) -> Option<String> {
+    None
+  }
+
+  /// Stores the output in the cache, replacing the output cached for other indentation.
+  fn set(&self, _indent: usize, _output: String) {
+    #[cfg(feature = "std")]
+    {
+      *self.lock() = Some((_indent, _output.into()));
+    }
+  }
+
+  /// Clears the cache.
+  fn clear(&mut self) {
+    #[cfg(feature = "std")]
+    {
+      *self.lock() = None;
+    }
+  }
+
+  /// Locks the cached output, a poisoned lock is still usable, because the output is always replaced as a whole.
+  #[cfg(feature = "std")]
+  fn lock(&self) -> std::sync::MutexGuard<'_, Option<(usize, std::sync::Arc<str>)>> {
+    self.output.lock().unwrap_or_else(|error| error.into_inner())
+  }
+}
+
+impl Clone for SerializationCache {
+  /// Clones the cache together with the cached output.
+  fn clone(&self) -> Self {
+    Self {
+      #[cfg(feature = "std")]
+      output: std::sync::Mutex::new(self.lock().clone()),
+    }
+  }
+}
+
+impl PartialEq for SerializationCache {
+  /// Caches are always equal, they do not affect the equality of elements.
+  fn eq(&self, _: &Self) -> bool {
+    true
+  }
+}
+
+impl Eq for SerializationCache {}
+
+/// Structure representing an attribute of the `HTML` element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlAttribute {
   name: String,
-  value: String,
+  value: AttributeValue,
 }
 
-#[derive(Debug, Clone)]
+impl HtmlAttribute {
+  /// Creates a new attribute with specified name and value.
+  pub fn new<T: ToString>(name: &str, value: T) -> Self {
+    Self {
+      name: name.to_string(),
+      value: AttributeValue::Str(value.to_string()),
+    }
+  }
+
+  /// Returns the name of the attribute.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Returns the value of the attribute, boolean attributes have an empty value.
+  pub fn value(&self) -> Cow<'_, str> {
+    match &self.value {
+      AttributeValue::Str(value) => Cow::Borrowed(value),
+      AttributeValue::Int(value) => Cow::Owned(value.to_string()),
+      AttributeValue::Bool => Cow::Borrowed(""),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HtmlElement {
   name: String,
   attributes: Vec<HtmlAttribute>,
   content: Option<String>,
   children: Vec<HtmlElement>,
   void: bool,
+  text: bool,
+  inline: Option<bool>,
+  cache: SerializationCache,
 }
 
 impl fmt::Display for HtmlElement {
@@ -129,7 +735,21 @@ impl HtmlElement {
       content: None,
       children: vec![],
       void: false,
+      text: false,
+      inline: None,
+      cache: SerializationCache::default(),
+    }
+  }
+
+  /// Creates a new `HTML` element with specified name, attributes and children,
+  /// for generating elements from data. Attribute values are used as is, like in [HtmlElement::set_attr].
+  pub fn from_parts(name: &str, attrs: Vec<(String, String)>, children: Vec<HtmlElement>) -> Self {
+    let mut element = Self::new(name);
+    for (attr_name, value) in attrs {
+      element.set_attr(&attr_name, value);
     }
+    element.append_children(children);
+    element
   }
 
   /// Creates a new `HTML` void element with specified name.
@@ -140,7 +760,46 @@ impl HtmlElement {
       content: None,
       children: vec![],
       void: true,
+      text: false,
+      inline: None,
+      cache: SerializationCache::default(),
+    }
+  }
+
+  /// Creates a new `HTML` void element with specified name, as an entry point of the fluent
+  /// builder chain, e.g. `HtmlElement::void("img").with_attr("src", "a.png")`; same as [HtmlElement::new_void].
+  pub fn void(name: &str) -> Self {
+    Self::new_void(name)
+  }
+
+  /// Creates a new text node with escaped text, serialized without any tags,
+  /// used to interleave text with children elements.
+  pub fn text(text: &str) -> Self {
+    Self {
+      name: "#text".to_string(),
+      attributes: vec![],
+      content: Some(escape_text(text).into_owned()),
+      children: vec![],
+      void: false,
+      text: true,
+      inline: None,
+      cache: SerializationCache::default(),
+    }
+  }
+
+  /// Creates text nodes from lines of the specified text, interleaved with `<br>` elements.
+  /// Empty lines produce no text nodes, but each line break produces a `<br>` element.
+  pub fn text_with_breaks(text: &str) -> Vec<HtmlElement> {
+    let mut elements = vec![];
+    for (i, line) in text.split('\n').enumerate() {
+      if i > 0 {
+        elements.push(Self::new_void("br"));
+      }
+      if !line.is_empty() {
+        elements.push(Self::text(line));
+      }
     }
+    elements
   }
 
   /// Creates a new `<div>` element.
@@ -152,88 +811,1518 @@ impl HtmlElement {
     element
   }
 
-  /// Sets an attribute of the `HTML` element.
-  pub fn set_attr<T: ToString>(&mut self, name: &str, value: T) {
-    self.attributes.push(HtmlAttribute {
-      name: name.to_string(),
-      value: value.to_string(),
-    })
+  /// Creates a new `<nav>` landmark element.
+  pub fn new_nav() -> Self {
+    Self::new("nav")
   }
 
-  /// Sets a `class` attribute of the `HTML` element.
-  pub fn set_class(&mut self, class: &str) {
-    self.attributes.push(HtmlAttribute {
-      name: "class".to_string(),
-      value: class.to_string(),
-    })
+  /// Creates a new `<main>` landmark element.
+  pub fn new_main() -> Self {
+    Self::new("main")
   }
 
-  /// Sets a `style` attribute of the `HTML` element.
-  pub fn set_style(&mut self, style: &str) {
-    self.attributes.push(HtmlAttribute {
-      name: "style".to_string(),
-      value: style.to_string(),
-    })
+  /// Creates a new `<header>` landmark element.
+  pub fn new_header() -> Self {
+    Self::new("header")
   }
 
-  /// Adds a child element.
-  pub fn add_child(&mut self, e: HtmlElement) {
-    self.children.push(e);
+  /// Creates a new `<footer>` landmark element.
+  pub fn new_footer() -> Self {
+    Self::new("footer")
   }
 
-  /// Adds an optional child element.
-  pub fn add_child_opt(&mut self, e: Option<HtmlElement>) {
-    if let Some(element) = e {
-      self.children.push(element);
+  /// Creates a new `<section>` element, labeled with `aria-label` attribute when label is specified.
+  pub fn new_section(label: Option<&str>) -> Self {
+    let mut element = Self::new("section");
+    if let Some(label) = label {
+      element.set_attr("aria-label", escape_attr(label));
     }
+    element
   }
 
-  /// Adds multiple children elements.
-  pub fn add_children(&mut self, elements: Vec<HtmlElement>) {
-    for element in elements {
-      self.children.push(element);
+  /// Creates a new breadcrumb navigation, each item is a label with escaped text
+  /// and an optional link target. The last item is marked as the current page.
+  pub fn new_breadcrumb(items: &[(&str, Option<&str>)]) -> Self {
+    let mut list = Self::new("ol");
+    for (index, (label, href)) in items.iter().enumerate() {
+      let mut item = Self::new("li");
+      if index + 1 == items.len() {
+        item.set_attr("aria-current", "page");
+      }
+      match href {
+        Some(href) => {
+          let mut link = Self::new("a").with_text(label);
+          link.set_url_attr("href", href);
+          item.add_child(link);
+        }
+        None => item.set_content(&escape_text(label)),
+      }
+      list.add_child(item);
     }
+    Self::new_nav().with_attr("aria-label", "breadcrumb").with_child(list)
   }
 
-  /// Sets the content of the `HTML` element.
-  pub fn set_content(&mut self, content: &str) {
-    self.content = Some(content.to_string());
+  /// Creates a new `<span>` element.
+  pub fn new_span(class: Option<&str>) -> Self {
+    let mut element = Self::new("span");
+    if let Some(class_name) = class {
+      element.set_class(class_name);
+    }
+    element
   }
 
-  /// Serializes the element to its textual representation.
-  pub fn write(&self, indent: usize, buffer: &mut String) {
-    let _ = write!(buffer, "{}<{}", indentation(indent), self.name );
-    for attribute in &self.attributes {
-      let _ = write!(buffer, r#" {}="{}""#, attribute.name, attribute.value);
-    }
+  /// Creates a new `<p>` element with escaped text content.
+  pub fn new_paragraph(text: &str) -> Self {
+    let mut element = Self::new("p");
+    element.set_content(&escape_text(text));
+    element
+  }
+
+  /// Creates a new `<details>` disclosure element with `<summary>` containing escaped text,
+  /// followed by specified content element. When `open` is `true`, the details are initially expanded.
+  pub fn new_details(summary: &str, content: HtmlElement, open: bool) -> Self {
+    let mut element = Self::new("details");
+    if open {
+      element.set_bool_attr("open");
+    }
+    let mut summary_element = Self::new("summary");
+    summary_element.set_content(&escape_text(summary));
+    element.add_child(summary_element);
+    element.add_child(content);
+    element
+  }
+
+  /// Creates a new `<picture>` element with `<source>` elements, one for each pair
+  /// of media query and source set, followed by the fallback `<img>` element.
+  pub fn new_picture(sources: &[(&str, &str)], fallback_src: &str, alt: &str) -> Self {
+    let mut element = Self::new("picture");
+    for (media, srcset) in sources {
+      element.add_child(Self::new_void("source").with_attr("media", escape_attr(media)).with_attr("srcset", escape_attr(srcset)));
+    }
+    let mut img = Self::new_void("img");
+    img.set_url_attr("src", fallback_src);
+    img.set_attr("alt", escape_attr(alt));
+    element.add_child(img);
+    element
+  }
+
+  /// Creates a new `<link>` element referencing an external stylesheet, with optional
+  /// subresource `integrity` hash and `crossorigin` mode, recommended for stylesheets served from CDNs.
+  pub fn new_stylesheet(href: &str, integrity: Option<&str>, crossorigin: Option<&str>) -> Self {
+    let mut element = Self::new_void("link").with_attr("rel", "stylesheet");
+    element.set_url_attr("href", href);
+    element.set_attr_opt("integrity", integrity.map(escape_attr));
+    element.set_attr_opt("crossorigin", crossorigin.map(escape_attr));
+    element
+  }
+
+  /// Creates a new `<script>` element referencing an external script, with optional
+  /// subresource `integrity` hash and `crossorigin` mode, recommended for scripts served from CDNs.
+  pub fn new_script(src: &str, integrity: Option<&str>, crossorigin: Option<&str>) -> Self {
+    let mut element = Self::new("script");
+    element.set_url_attr("src", src);
+    element.set_attr_opt("integrity", integrity.map(escape_attr));
+    element.set_attr_opt("crossorigin", crossorigin.map(escape_attr));
+    element
+  }
+
+  /// Creates a new `<div>` containing a `<label>` linked with `for` attribute
+  /// to the following `<input>` element of specified type, identified by `id`.
+  pub fn new_labeled_input(id: &str, label: &str, input_type: &str, name: &str) -> Self {
+    let label = Self::new("label").with_attr("for", escape_attr(id)).with_text(label);
+    let input = Self::new_void("input")
+      .with_attr("type", escape_attr(input_type))
+      .with_attr("id", escape_attr(id))
+      .with_attr("name", escape_attr(name));
+    Self::new_div(None).with_child(label).with_child(input)
+  }
+
+  /// Creates a new `<audio>` or `<video>` element with `<source>` elements, one for each pair
+  /// of source URL and media type, followed by escaped fallback text displayed by browsers
+  /// not supporting the media element. When `controls` is `true`, playback controls are displayed.
+  pub fn new_media(kind: MediaKind, sources: &[(&str, &str)], controls: bool, fallback: &str) -> Self {
+    let mut element = Self::new(kind.as_str());
+    if controls {
+      element.set_bool_attr("controls");
+    }
+    for (src, media_type) in sources {
+      let mut source = Self::new_void("source");
+      source.set_url_attr("src", src);
+      source.set_attr("type", escape_attr(media_type));
+      element.add_child(source);
+    }
+    if !fallback.is_empty() {
+      element.add_child(Self::text(fallback));
+    }
+    element
+  }
+
+  /// Wraps the inner element in an `<a>` element linking to `href` when present,
+  /// otherwise returns the inner element unchanged.
+  pub fn maybe_linked(href: Option<&str>, inner: HtmlElement) -> HtmlElement {
+    match href {
+      Some(href) => {
+        let mut link = inner.wrap("a", None);
+        link.set_url_attr("href", href);
+        link
+      }
+      None => inner,
+    }
+  }
+
+  /// Creates a new `<table>` with optional `<colgroup>` defining the columns,
+  /// `<thead>` row with escaped header texts and `<tbody>` rows with escaped cell texts.
+  ///
+  /// The `<colgroup>` is omitted when there are no column definitions,
+  /// and the `<thead>` is omitted when there are no headers.
+  pub fn new_table(columns: &[ColSpec], headers: &[&str], rows: &[&[&str]]) -> Self {
+    let mut table = Self::new("table");
+    if !columns.is_empty() {
+      let mut colgroup = Self::new("colgroup");
+      for column in columns {
+        let mut col = Self::new_void("col");
+        col.set_attr_opt("style", column.width.map(|width| format!("width: {}", escape_attr(width))));
+        if let Some(class) = column.class {
+          col.set_class(&escape_attr(class));
+        }
+        colgroup.add_child(col);
+      }
+      table.add_child(colgroup);
+    }
+    if !headers.is_empty() {
+      let mut row = Self::new("tr");
+      row.append_children(headers.iter().map(|header| Self::new("th").with_text(header)));
+      table.add_child(Self::new("thead").with_child(row));
+    }
+    let mut body = Self::new("tbody");
+    for cells in rows {
+      let mut row = Self::new("tr");
+      row.append_children(cells.iter().map(|cell| Self::new("td").with_text(cell)));
+      body.add_child(row);
+    }
+    table.with_child(body)
+  }
+
+  /// Creates a new `<noscript>` element containing specified fallback element,
+  /// displayed when scripting is disabled.
+  pub fn new_noscript(fallback: HtmlElement) -> Self {
+    Self::new("noscript").with_child(fallback)
+  }
+
+  /// Creates a new `<meta>` element with `name` and `content` attributes.
+  pub fn new_meta(name: &str, content: &str) -> Self {
+    let mut element = Self::new_void("meta");
+    element.set_attr("name", escape_attr(name));
+    element.set_attr("content", escape_attr(content));
+    element
+  }
+
+  /// Creates a new `<meta>` element with `property` and `content` attributes,
+  /// like used by Open Graph (`og:`) tags.
+  pub fn new_meta_property(property: &str, content: &str) -> Self {
+    let mut element = Self::new_void("meta");
+    element.set_attr("property", escape_attr(property));
+    element.set_attr("content", escape_attr(content));
+    element
+  }
+
+  /// Sets an attribute of the `HTML` element.
+  ///
+  /// When serialized, the value is enclosed in single quotes if it contains
+  /// double quotes but no single quotes, otherwise the value is enclosed
+  /// in double quotes and all double quotes in the value are escaped.
+  pub fn set_attr<T: ToString>(&mut self, name: &str, value: T) {
+    self.invalidate();
+    self.attributes.push(HtmlAttribute {
+      name: name.to_string(),
+      value: AttributeValue::Str(value.to_string()),
+    })
+  }
+
+  /// Sets an attribute of the `HTML` element, when the value is present.
+  pub fn set_attr_opt<T: ToString>(&mut self, name: &str, value: Option<T>) {
+    if let Some(value) = value {
+      self.set_attr(name, value);
+    }
+  }
+
+  /// Sets an attribute of the `HTML` element, when the condition is `true`.
+  pub fn set_attr_if<T: ToString>(&mut self, cond: bool, name: &str, value: T) {
+    if cond {
+      self.set_attr(name, value);
+    }
+  }
+
+  /// Sets a `data-src` attribute to the source location of the caller, formatted as `file:line:column`.
+  ///
+  /// This helps to find the code that built the element, when debugging generated documents.
+  #[track_caller]
+  pub fn set_source_location(&mut self) {
+    let location = core::panic::Location::caller();
+    self.set_attr("data-src", format!("{}:{}:{}", location.file(), location.line(), location.column()));
+  }
+
+  /// Adds an attribute to the `HTML` element.
+  pub fn add_attribute(&mut self, attr: HtmlAttribute) {
+    self.invalidate();
+    self.attributes.push(attr);
+  }
+
+  /// Returns the attributes of the `HTML` element.
+  pub fn attributes(&self) -> &[HtmlAttribute] {
+    &self.attributes
+  }
+
+  /// Sets an attribute containing URL (like `href` or `src`), percent-encoding
+  /// spaces, non-ASCII and other characters not allowed in URLs.
+  pub fn set_url_attr(&mut self, name: &str, url: &str) {
+    self.set_attr(name, percent_encode_url(url));
+  }
+
+  /// Sets a `srcset` attribute from pairs of image URL and descriptor (like `2x` or `480w`),
+  /// joined as `url descriptor, url descriptor`. URLs are percent-encoded, so spaces and commas
+  /// within URLs do not break the list, and the whole value is escaped.
+  pub fn set_srcset(&mut self, entries: &[(&str, &str)]) {
+    let value = entries
+      .iter()
+      .map(|(url, descriptor)| {
+        let url = percent_encode_url(url).replace(',', "%2C");
+        if descriptor.is_empty() {
+          url
+        } else {
+          format!("{} {}", url, descriptor)
+        }
+      })
+      .collect::<Vec<String>>()
+      .join(", ");
+    self.set_attr("srcset", escape_attr(&value));
+  }
+
+  /// Sets an inline event handler attribute `on{event}` with escaped handler code,
+  /// e.g. `set_event("click", "toggle()")` sets `onclick` attribute.
+  ///
+  /// Returns an error and leaves the element unchanged, when the event name
+  /// is not lowercase alphabetic, so the name can not inject other attributes.
+  pub fn set_event(&mut self, event: &str, handler: &str) -> Result<(), ValidationIssue> {
+    if event.is_empty() || !event.chars().all(|ch| ch.is_ascii_lowercase()) {
+      return Err(ValidationIssue::error(&self.name, &format!("has invalid event name '{}'", event)));
+    }
+    self.set_attr(&format!("on{}", event), escape_attr(handler));
+    Ok(())
+  }
+
+  /// Sets an integer attribute of the `HTML` element.
+  pub fn set_attr_int(&mut self, name: &str, value: i64) {
+    self.invalidate();
+    self.attributes.push(HtmlAttribute {
+      name: name.to_string(),
+      value: AttributeValue::Int(value),
+    })
+  }
+
+  /// Sets a boolean attribute of the `HTML` element, serialized without value.
+  pub fn set_bool_attr(&mut self, name: &str) {
+    self.invalidate();
+    self.attributes.push(HtmlAttribute {
+      name: name.to_string(),
+      value: AttributeValue::Bool,
+    })
+  }
+
+  /// Sets whether the children of the element are serialized inline, on the same line
+  /// as the element tags, without indentation. By default children of phrasing elements
+  /// like `<a>`, `<b>`, `<span>` or `<code>` are serialized inline.
+  pub fn set_inline(&mut self, inline: bool) {
+    self.invalidate();
+    self.inline = Some(inline);
+  }
+
+  /// Returns `true` when the children of the element are serialized inline.
+  fn is_inline(&self) -> bool {
+    self.inline.unwrap_or_else(|| is_phrasing(&self.name))
+  }
+
+  /// Sets the `tabindex` attribute, replacing any existing one.
+  ///
+  /// A negative value makes the element focusable but not reachable with sequential keyboard navigation,
+  /// zero places the element in the navigation order defined by the document structure,
+  /// and positive values set an explicit navigation order (generally discouraged).
+  pub fn set_tabindex(&mut self, index: i32) {
+    self.replace_attr("tabindex", Some(AttributeValue::Int(index.into())));
+  }
+
+  /// Sets or removes the boolean `hidden` attribute, serialized without value.
+  pub fn set_hidden(&mut self, hidden: bool) {
+    self.replace_attr("hidden", hidden.then_some(AttributeValue::Bool));
+  }
+
+  /// Sets the enumerated `draggable` attribute, serialized as `draggable="true"` or `draggable="false"`.
+  pub fn set_draggable(&mut self, draggable: bool) {
+    self.replace_attr("draggable", Some(AttributeValue::Str(draggable.to_string())));
+  }
+
+  /// Sets the enumerated `contenteditable` attribute, serialized as `contenteditable="true"` or `contenteditable="false"`.
+  pub fn set_contenteditable(&mut self, editable: bool) {
+    self.replace_attr("contenteditable", Some(AttributeValue::Str(editable.to_string())));
+  }
+
+  /// Removes all attributes with specified name and appends the new value, if present.
+  fn replace_attr(&mut self, name: &str, value: Option<AttributeValue>) {
+    self.invalidate();
+    self.attributes.retain(|attribute| attribute.name != name);
+    if let Some(value) = value {
+      self.attributes.push(HtmlAttribute { name: name.to_string(), value });
+    }
+  }
+
+  /// Sets a `class` attribute of the `HTML` element.
+  ///
+  /// The value of already existing `class` attribute is replaced, so the element
+  /// has always a single `class` attribute. Use [HtmlElement::add_class] to append class names.
+  pub fn set_class(&mut self, class: &str) {
+    self.invalidate();
+    let mut found = false;
+    self.attributes.retain_mut(|attribute| {
+      if attribute.name != "class" {
+        return true;
+      }
+      if !found {
+        attribute.value = AttributeValue::Str(class.to_string());
+        found = true;
+        return true;
+      }
+      false
+    });
+    if !found {
+      self.attributes.push(HtmlAttribute {
+        name: "class".to_string(),
+        value: AttributeValue::Str(class.to_string()),
+      })
+    }
+  }
+
+  /// Adds class names to the `class` attribute of the `HTML` element.
+  ///
+  /// The specified text is split on whitespace, and each resulting class name
+  /// not yet present in the `class` attribute is appended to it,
+  /// so `add_class("foo bar")` adds both `foo` and `bar` class names.
+  pub fn add_class(&mut self, class: &str) {
+    self.invalidate();
+    if let Some(AttributeValue::Str(value)) = self.attributes.iter_mut().find(|attribute| attribute.name == "class").map(|attribute| &mut attribute.value) {
+      append_class_names(value, class);
+    } else {
+      let mut value = String::new();
+      append_class_names(&mut value, class);
+      if !value.is_empty() {
+        self.set_class(&value);
+      }
+    }
+  }
+
+  /// Adds class names whose condition is `true` to the `class` attribute, like [HtmlElement::add_class].
+  pub fn set_classes_conditional(&mut self, pairs: &[(&str, bool)]) {
+    for (class, _) in pairs.iter().filter(|(_, cond)| *cond) {
+      self.add_class(class);
+    }
+  }
+
+  /// Merges attributes of other element into this element.
+  ///
+  /// Class names of other element are added to the `class` attribute of this element,
+  /// values of all other attributes replace values of attributes with the same name.
+  pub fn merge_attrs(&mut self, other: &HtmlElement) {
+    self.invalidate();
+    for attribute in &other.attributes {
+      match &attribute.value {
+        AttributeValue::Str(value) if attribute.name == "class" => self.add_class(value),
+        _ => {
+          if let Some(existing) = self.attributes.iter_mut().find(|existing| existing.name == attribute.name) {
+            existing.value = attribute.value.clone();
+          } else {
+            self.attributes.push(attribute.clone());
+          }
+        }
+      }
+    }
+  }
+
+  /// Returns `true` when the `HTML` element has the specified class name.
+  pub fn has_class(&self, class_name: &str) -> bool {
+    self.attributes.iter().any(|attribute| match &attribute.value {
+      AttributeValue::Str(value) if attribute.name == "class" => value.split_whitespace().any(|name| name == class_name),
+      _ => false,
+    })
+  }
+
+  /// Sets an escaped `title` attribute (tooltip) of the `HTML` element.
+  pub fn set_title(&mut self, text: &str) {
+    self.set_attr("title", escape_attr(text));
+  }
+
+  /// Sets a `style` attribute of the `HTML` element.
+  pub fn set_style(&mut self, style: &str) {
+    self.invalidate();
+    self.attributes.push(HtmlAttribute {
+      name: "style".to_string(),
+      value: AttributeValue::Str(style.to_string()),
+    })
+  }
+
+  /// Adds a child element and returns a mutable reference to it.
+  ///
+  /// In debug builds, adding a child to a void element panics,
+  /// because children of void elements are never serialized.
+  pub fn add_child(&mut self, e: HtmlElement) -> &mut HtmlElement {
+    debug_assert!(!self.void, "void element <{}> can not have children", self.name);
+    self.invalidate();
+    self.children.push(e);
+    self.children.last_mut().unwrap()
+  }
+
+  /// Inserts a child element at specified position and returns a mutable reference to it.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `index` is greater than the number of children.
+  /// In debug builds, inserting a child into a void element panics too.
+  pub fn insert_child(&mut self, index: usize, e: HtmlElement) -> &mut HtmlElement {
+    debug_assert!(!self.void, "void element <{}> can not have children", self.name);
+    self.invalidate();
+    self.children.insert(index, e);
+    &mut self.children[index]
+  }
+
+  /// Inserts a child element before all other children and returns a mutable reference to it.
+  pub fn prepend_child(&mut self, e: HtmlElement) -> &mut HtmlElement {
+    self.insert_child(0, e)
+  }
+
+  /// Removes the child element at specified position and returns it,
+  /// or returns `None` when `index` is out of range.
+  pub fn remove_child(&mut self, index: usize) -> Option<HtmlElement> {
+    self.invalidate();
+    (index < self.children.len()).then(|| self.children.remove(index))
+  }
+
+  /// Replaces the child element at specified position and returns the displaced child,
+  /// or returns `None` and leaves the children unchanged when `index` is out of range.
+  pub fn replace_child(&mut self, index: usize, e: HtmlElement) -> Option<HtmlElement> {
+    self.invalidate();
+    self.children.get_mut(index).map(|child| core::mem::replace(child, e))
+  }
+
+  /// Retains only the children elements for which the predicate returns `true`.
+  pub fn retain_children<F: Fn(&HtmlElement) -> bool>(&mut self, pred: F) {
+    self.invalidate();
+    self.children.retain(pred);
+  }
+
+  /// Adds an optional child element.
+  pub fn add_child_opt(&mut self, e: Option<HtmlElement>) {
+    if let Some(element) = e {
+      self.add_child(element);
+    }
+  }
+
+  /// Adds multiple children elements.
+  pub fn add_children(&mut self, elements: Vec<HtmlElement>) {
+    self.append_children(elements);
+  }
+
+  /// Appends all elements from specified iterator as last children, preserving their order.
+  pub fn append_children(&mut self, elements: impl IntoIterator<Item = HtmlElement>) {
+    for element in elements {
+      self.add_child(element);
+    }
+  }
+
+  /// Inserts all elements from specified iterator as first children, preserving their order.
+  pub fn prepend_children(&mut self, elements: impl IntoIterator<Item = HtmlElement>) {
+    let mut elements = elements.into_iter().peekable();
+    if elements.peek().is_some() {
+      debug_assert!(!self.void, "void element <{}> can not have children", self.name);
+      self.invalidate();
+      self.children.splice(0..0, elements);
+    }
+  }
+
+  /// Removes all children elements and returns them.
+  pub fn take_children(&mut self) -> Vec<HtmlElement> {
+    self.invalidate();
+    core::mem::take(&mut self.children)
+  }
+
+  /// Sets the content of the `HTML` element.
+  ///
+  /// The content is raw `HTML` serialized without escaping,
+  /// use [HtmlElement::with_text] or [escape_text] for plain text.
+  /// When the element has also children, the content is serialized
+  /// as leading text, always preceding all children.
+  pub fn set_content(&mut self, content: &str) {
+    self.invalidate();
+    self.content = Some(content.to_string());
+  }
+
+  /// Sets an attribute and returns the element, for chaining.
+  pub fn with_attr<T: ToString>(mut self, name: &str, value: T) -> Self {
+    self.set_attr(name, value);
+    self
+  }
+
+  /// Sets an attribute when the value is present and returns the element, for chaining.
+  pub fn with_attr_opt<T: ToString>(mut self, name: &str, value: Option<T>) -> Self {
+    self.set_attr_opt(name, value);
+    self
+  }
+
+  /// Sets an attribute when the condition is `true` and returns the element, for chaining.
+  pub fn with_attr_if<T: ToString>(mut self, cond: bool, name: &str, value: T) -> Self {
+    self.set_attr_if(cond, name, value);
+    self
+  }
+
+  /// Sets a `data-src` attribute to the source location of the caller and returns the element, for chaining.
+  #[track_caller]
+  pub fn with_source_location(mut self) -> Self {
+    self.set_source_location();
+    self
+  }
+
+  /// Sets a `class` attribute and returns the element, for chaining.
+  pub fn with_class(mut self, class: &str) -> Self {
+    self.set_class(class);
+    self
+  }
+
+  /// Sets an escaped `title` attribute and returns the element, for chaining.
+  pub fn with_title(mut self, text: &str) -> Self {
+    self.set_title(text);
+    self
+  }
+
+  /// Adds a child element and returns the element, for chaining.
+  pub fn with_child(mut self, e: HtmlElement) -> Self {
+    self.add_child(e);
+    self
+  }
+
+  /// Inserts a child element before all other children and returns the element, for chaining.
+  pub fn with_prepended_child(mut self, e: HtmlElement) -> Self {
+    self.prepend_child(e);
+    self
+  }
+
+  /// Sets the escaped text as the content and returns the element, for chaining.
+  pub fn with_text(mut self, text: &str) -> Self {
+    self.set_content(&escape_text(text));
+    self
+  }
+
+  /// Sets the raw `HTML` as the content (without escaping) and returns the element, for chaining.
+  pub fn with_raw(mut self, html: &str) -> Self {
+    self.set_content(html);
+    self
+  }
+
+  /// Wraps this element in a new element with specified name and optional class,
+  /// returning the wrapper containing this element as its only child.
+  pub fn wrap(self, wrapper_name: &str, class: Option<&str>) -> HtmlElement {
+    let mut wrapper = HtmlElement::new(wrapper_name);
+    if let Some(class_name) = class {
+      wrapper.set_class(class_name);
+    }
+    wrapper.with_child(self)
+  }
+
+  /// Discards this element's own tags and returns its children, the inverse of [HtmlElement::wrap].
+  ///
+  /// Content of the element (if any) is preserved as a leading text node.
+  pub fn unwrap_into(mut self) -> Vec<HtmlElement> {
+    let mut children = core::mem::take(&mut self.children);
+    if let Some(content) = self.content.take().filter(|content| !content.is_empty()) {
+      let mut text = Self::text("");
+      text.content = Some(content);
+      children.insert(0, text);
+    }
+    children
+  }
+
+  /// Returns the name of the `HTML` element.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Clears the cached output of the element, called by all methods modifying the element.
+  fn invalidate(&mut self) {
+    self.cache.clear();
+  }
+
+  /// Returns a mutable reference to the child element at specified position, if present.
+  ///
+  /// Only the cached output of this element is invalidated, so editing a descendant
+  /// reached by chained calls keeps the cached output of all unchanged siblings.
+  pub fn child_mut(&mut self, index: usize) -> Option<&mut HtmlElement> {
+    self.invalidate();
+    self.children.get_mut(index)
+  }
+
+  /// Returns `true` when the output of the element is cached for specified indentation.
+  #[cfg(all(test, feature = "std"))]
+  pub(crate) fn is_cached(&self, indent: usize) -> bool {
+    self.cache.get(indent).is_some()
+  }
+
+  /// Returns mutable children of the element, invalidating its cached output.
+  /// Unlike [HtmlElement::add_child], children of void elements are not checked in debug builds.
+  pub(crate) fn children_mut(&mut self) -> &mut Vec<HtmlElement> {
+    self.invalidate();
+    &mut self.children
+  }
+
+  /// Returns `true` when the `HTML` element has no attributes, no content and no children.
+  pub fn is_empty(&self) -> bool {
+    self.attributes.is_empty() && self.content.as_ref().is_none_or(|content| content.is_empty()) && self.children.is_empty()
+  }
+
+  /// Returns `true` when the `HTML` element has an attribute with specified name.
+  pub fn has_attr(&self, name: &str) -> bool {
+    self.attributes.iter().any(|attribute| attribute.name == name)
+  }
+
+  /// Returns the first direct child satisfying the predicate.
+  pub fn find_child<F: Fn(&HtmlElement) -> bool>(&self, pred: F) -> Option<&HtmlElement> {
+    self.children.iter().find(|child| pred(child))
+  }
+
+  /// Returns the first descendant (in pre-order) satisfying the predicate.
+  pub fn find_descendant<F: Fn(&HtmlElement) -> bool>(&self, pred: F) -> Option<&HtmlElement> {
+    self.find_descendant_ref(&pred)
+  }
+
+  /// Returns the first descendant satisfying the predicate passed by reference.
+  fn find_descendant_ref<F: Fn(&HtmlElement) -> bool>(&self, pred: &F) -> Option<&HtmlElement> {
+    for child in &self.children {
+      if pred(child) {
+        return Some(child);
+      }
+      if let Some(descendant) = child.find_descendant_ref(pred) {
+        return Some(descendant);
+      }
+    }
+    None
+  }
+
+  /// Returns an iterator over all descendants of this element (excluding the element itself)
+  /// in document order, i.e. in pre-order.
+  pub fn descendants(&self) -> impl Iterator<Item = &HtmlElement> {
+    Descendants {
+      stack: self.children.iter().rev().collect(),
+    }
+  }
+
+  /// Visits this element and all its descendants in pre-order.
+  pub fn walk<F: FnMut(&HtmlElement)>(&self, f: &mut F) {
+    f(self);
+    for child in &self.children {
+      child.walk(f);
+    }
+  }
+
+  /// Visits this element and all its descendants in pre-order, allowing modifications.
+  ///
+  /// The cached output of all visited elements is invalidated, use [HtmlElement::child_mut]
+  /// to modify a single descendant and keep the cached output of unchanged elements.
+  pub fn walk_mut<F: FnMut(&mut HtmlElement)>(&mut self, f: &mut F) {
+    self.invalidate();
+    f(self);
+    for child in &mut self.children {
+      child.walk_mut(f);
+    }
+  }
+
+  /// Compares this element with the other one, returning all structural differences
+  /// found in both trees, with paths to the differing elements.
+  ///
+  /// Attributes are compared by name and serialized value regardless of their order,
+  /// children are compared pairwise by position.
+  pub fn diff(&self, other: &HtmlElement) -> Vec<Difference> {
+    let mut differences = vec![];
+    self.diff_at(other, &self.name, &mut differences);
+    differences
+  }
+
+  /// Collects structural differences between this element and the other one located at specified path.
+  fn diff_at(&self, other: &HtmlElement, path: &str, differences: &mut Vec<Difference>) {
+    if self.name != other.name {
+      differences.push(Difference::new(path, DifferenceKind::NameChanged { old: self.name.clone(), new: other.name.clone() }));
+    }
+    for attribute in &self.attributes {
+      match other.attributes.iter().find(|other_attribute| other_attribute.name == attribute.name) {
+        Some(other_attribute) if other_attribute.value() != attribute.value() => differences.push(Difference::new(
+          path,
+          DifferenceKind::AttributeChanged {
+            name: attribute.name.clone(),
+            old: attribute.value().into_owned(),
+            new: other_attribute.value().into_owned(),
+          },
+        )),
+        Some(_) => {}
+        None => differences.push(Difference::new(path, DifferenceKind::AttributeRemoved { name: attribute.name.clone() })),
+      }
+    }
+    for other_attribute in other.attributes.iter().filter(|other_attribute| !self.has_attr(&other_attribute.name)) {
+      differences.push(Difference::new(
+        path,
+        DifferenceKind::AttributeAdded {
+          name: other_attribute.name.clone(),
+          value: other_attribute.value().into_owned(),
+        },
+      ));
+    }
+    if self.content != other.content {
+      differences.push(Difference::new(path, DifferenceKind::ContentChanged));
+    }
+    if self.children.len() != other.children.len() {
+      differences.push(Difference::new(
+        path,
+        DifferenceKind::ChildCountChanged {
+          old: self.children.len(),
+          new: other.children.len(),
+        },
+      ));
+    }
+    for (index, (child, other_child)) in self.children.iter().zip(&other.children).enumerate() {
+      child.diff_at(other_child, &format!("{}/{}[{}]", path, child.name, index), differences);
+    }
+  }
+
+  /// Validates this element and all its descendants, returning all found issues.
+  ///
+  /// Obsolete elements (like `<center>` or `<font>`) and presentational attributes
+  /// (like `bgcolor` or `align`) are reported as warnings, all other issues are errors.
+  ///
+  /// Void elements having content or children are reported as errors,
+  /// because their content and children are never serialized.
+  pub fn validate(&self) -> Vec<ValidationIssue> {
+    let mut issues = vec![];
+    self.walk(&mut |element| {
+      if element.void && element.content.is_some() {
+        issues.push(ValidationIssue::error(&element.name, "void element has content"));
+      }
+      if element.void && !element.children.is_empty() {
+        issues.push(ValidationIssue::error(&element.name, "void element has children"));
+      }
+      if is_obsolete(&element.name) {
+        issues.push(ValidationIssue::warning(&element.name, "obsolete element"));
+      }
+      for attribute in element.attributes.iter().filter(|attribute| is_presentational(&attribute.name)) {
+        issues.push(ValidationIssue::warning(&element.name, &format!("obsolete presentational attribute '{}'", attribute.name)));
+      }
+    });
+    issues
+  }
+
+  /// Compares this element with other element structurally,
+  /// treating the attributes of each element as an unordered set,
+  /// attribute values are compared as serialized, e.g. `2` set as text equals integer `2`.
+  pub fn eq_ignoring_attr_order(&self, other: &HtmlElement) -> bool {
+    if self.name != other.name || self.void != other.void || self.text != other.text || self.content != other.content {
+      return false;
+    }
+    if self.attributes.len() != other.attributes.len() || self.children.len() != other.children.len() {
+      return false;
+    }
+    let mut attributes = self.attributes.iter().map(|a| (&a.name, a.value())).collect::<Vec<_>>();
+    let mut other_attributes = other.attributes.iter().map(|a| (&a.name, a.value())).collect::<Vec<_>>();
+    attributes.sort();
+    other_attributes.sort();
+    attributes == other_attributes && self.children.iter().zip(&other.children).all(|(a, b)| a.eq_ignoring_attr_order(b))
+  }
+
+  /// Sets the content of the `HTML` element wrapped in a `CDATA` section.
+  ///
+  /// This is intended for inline `<script>` and `<style>` elements in `XHTML` documents,
+  /// where the content is not escaped. Any `]]>` sequence in the content is split
+  /// between two consecutive `CDATA` sections.
+  pub fn set_cdata(&mut self, content: &str) {
+    self.invalidate();
+    self.content = Some(format!("<![CDATA[{}]]>", content.replace("]]>", "]]]]><![CDATA[>")));
+  }
+
+  /// Sets the content from specified template, replacing `{key}` placeholders with values of matching keys.
+  ///
+  /// Both the template text and substituted values are escaped. Placeholders
+  /// without a matching key are preserved as they are.
+  pub fn set_content_template(&mut self, template: &str, values: &[(&str, &str)]) {
+    let mut content = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+      content.push_str(&escape_text(&rest[..start]));
+      let placeholder = &rest[start..];
+      match placeholder.find('}') {
+        Some(end) => {
+          let key = &placeholder[1..end];
+          match values.iter().find(|(name, _)| *name == key) {
+            Some((_, value)) => content.push_str(&escape_text(value)),
+            None => content.push_str(&escape_text(&placeholder[..=end])),
+          }
+          rest = &placeholder[end + 1..];
+        }
+        None => {
+          content.push_str(&escape_text(placeholder));
+          rest = "";
+        }
+      }
+    }
+    content.push_str(&escape_text(rest));
+    self.set_content(&content);
+  }
+
+  /// Sets the serialized specified element as the raw content of this element, without escaping.
+  ///
+  /// Multi-line output is indented like children when this element is serialized.
+  pub fn set_inner(&mut self, element: &HtmlElement) {
+    self.set_content(&element.to_string());
+  }
+
+  /// Appends the specified text to the content of the `HTML` element.
+  pub fn append_content(&mut self, content: &str) {
+    self.invalidate();
+    self.content.get_or_insert_with(String::new).push_str(content);
+  }
+
+  /// Serializes the element to its textual representation.
+  pub fn write(&self, indent: usize, buffer: &mut String) {
+    let _ = self.write_element(indent, true, Context::new(&SerializeOptions::default()), buffer);
+  }
+
+  /// Serializes the element to its textual representation using specified options.
+  pub fn write_with(&self, indent: usize, opts: &SerializeOptions, buffer: &mut String) {
+    let _ = self.write_element(indent, true, Context::new(opts), buffer);
+  }
+
+  /// Serializes the element to its textual representation, after applying
+  /// specified hook to a copy of this element and each of its descendants.
+  ///
+  /// The element itself is left unchanged, so the hook may be used for cross-cutting
+  /// output modifications, like adding nonces to scripts or rewriting URLs.
+  pub fn write_with_hook<F: FnMut(&mut HtmlElement)>(&self, indent: usize, buffer: &mut String, mut hook: F) {
+    let mut element = self.clone();
+    element.walk_mut(&mut hook);
+    element.write(indent, buffer);
+  }
+
+  /// Serializes the element to its textual representation, reusing cached output
+  /// of this element and all its descendants that were not modified since the last call.
+  ///
+  /// Caching is opt-in, only this method reads and populates the cache, and only one
+  /// indentation is cached per element. Caching is available with `std` feature,
+  /// otherwise this method behaves like [HtmlElement::write].
+  pub fn write_cached(&self, indent: usize, buffer: &mut String) {
+    let _ = self.write_element(indent, true, Context::new(&SerializeOptions::default()).with_cache(), buffer);
+  }
+
+  /// Serializes the element to its textual representation,
+  /// without indentation of the first line.
+  ///
+  /// The remaining lines are indented like in [HtmlElement::write],
+  /// so the serialized element aligns to the position where it is placed.
+  pub fn write_no_leading_indent(&self, indent: usize, buffer: &mut String) {
+    let _ = self.write_element(indent, false, Context::new(&SerializeOptions::default()), buffer);
+  }
+
+  /// Serializes the element with default options, appending the output to specified buffer.
+  ///
+  /// The buffer capacity is reserved upfront using [HtmlElement::estimated_len],
+  /// so appending multiple elements into one buffer avoids most reallocations.
+  pub fn write_into(&self, buffer: &mut String) {
+    buffer.reserve(self.estimated_len());
+    self.write(0, buffer);
+  }
+
+  /// Returns an estimated length of the serialized element, without wrapping content.
+  ///
+  /// The estimate is close to the actual length, but not guaranteed to be exact,
+  /// it is intended for preallocating buffers.
+  pub fn estimated_len(&self) -> usize {
+    self.estimate(0)
+  }
+
+  /// Returns an estimated length of the serialized element at specified indentation.
+  fn estimate(&self, indent: usize) -> usize {
+    let content_len = self.content.as_ref().map_or(0, |content| content.len() + content.lines().count() * (indent + INDENT + 1));
+    if self.text {
+      return indent + content_len;
+    }
+    let attributes_len = self
+      .attributes
+      .iter()
+      .map(|attribute| match &attribute.value {
+        AttributeValue::Str(value) => attribute.name.len() + value.len() + 4,
+        AttributeValue::Int(_) => attribute.name.len() + 8,
+        AttributeValue::Bool => attribute.name.len() + 1,
+      })
+      .sum::<usize>();
+    let children_len = self.children.iter().map(|child| child.estimate(indent + INDENT) + 1).sum::<usize>();
+    2 * indent + 2 * self.name.len() + 6 + attributes_len + content_len + children_len
+  }
+
+  /// Serializes the element with tag names, attribute names, attribute values
+  /// and text content highlighted with ANSI colors, for printing in a terminal.
+  #[cfg(feature = "highlight")]
+  pub fn to_highlighted_string(&self) -> String {
+    let mut buffer = String::new();
+    let _ = self.write_element(0, true, Context::new(&SerializeOptions::default()).with_highlight(), &mut buffer);
+    buffer
+  }
+
+  /// Returns the exact length in bytes of the element serialized with default options,
+  /// without building the serialized text.
+  pub fn serialized_len(&self) -> usize {
+    self.serialized_len_with(&SerializeOptions::default())
+  }
+
+  /// Returns the exact length in bytes of the element serialized with specified options,
+  /// without building the serialized text.
+  pub fn serialized_len_with(&self, opts: &SerializeOptions) -> usize {
+    let mut writer = CountingWriter::default();
+    let _ = self.write_element(0, true, Context::new(opts), &mut writer);
+    writer.0
+  }
+
+  /// Returns the hash of the serialized element, computed like [HtmlDocument::content_hash].
+  pub fn content_hash(&self) -> u64 {
+    let mut writer = HashingWriter::default();
+    let _ = self.write_element(0, true, Context::new(&SerializeOptions::default()), &mut writer);
+    writer.0
+  }
+
+  /// Serializes the element into `UTF-8` bytes.
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut writer = BytesWriter::default();
+    let _ = self.write_element(0, true, Context::new(&SerializeOptions::default()), &mut writer);
+    writer.0
+  }
+
+  /// Serializes the content and children of the element, without its own tags.
+  pub fn inner_html(&self) -> String {
+    let mut buffer = String::new();
+    let _ = self.write_inner(&SerializeOptions::default(), &mut buffer);
+    buffer
+  }
+
+  /// Serializes the content and children of the element at zero indentation.
+  fn write_inner<W: fmt::Write>(&self, opts: &SerializeOptions, w: &mut W) -> fmt::Result {
+    if self.is_inline() {
+      // inline elements join the content and children without line breaks
+      if let Some(content) = &self.content {
+        write_text(w, &guard_raw_text(&self.name, content), opts, is_raw_text(&self.name))?;
+      }
+      for child in &self.children {
+        child.write_element(0, false, Context::new(opts).within(self).inlined(), w)?;
+      }
+      return Ok(());
+    }
+    let mut first = true;
+    if let Some(content) = self.content.as_ref().filter(|content| !content.is_empty()) {
+      for line in guard_raw_text(&self.name, content).lines() {
+        if !first {
+          writeln!(w)?;
+        }
+        write_text(w, line, opts, is_raw_text(&self.name))?;
+        first = false;
+      }
+    }
+    for child in &self.children {
+      if !first {
+        writeln!(w)?;
+      }
+      child.write_element(0, true, Context::new(opts).within(self), w)?;
+      first = false;
+    }
+    Ok(())
+  }
+
+  /// Serializes the element into the specified writer.
+  ///
+  /// Writing stops at the first error, which is returned to the caller.
+  #[cfg(feature = "std")]
+  pub fn write_to<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+    self.write_to_with(writer, &SerializeOptions::default())
+  }
+
+  /// Serializes the indented element into the specified writer, like [HtmlElement::write_to].
+  #[cfg(feature = "std")]
+  pub fn to_writer_pretty<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+    self.write_to(writer)
+  }
+
+  /// Serializes the minified element into the specified writer.
+  ///
+  /// Writing stops at the first error, which is returned to the caller.
+  #[cfg(feature = "std")]
+  pub fn to_writer_minified<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+    self.write_to_with(writer, &SerializeOptions { minify: true, ..Default::default() })
+  }
+
+  /// Serializes the element into the specified writer using specified options.
+  #[cfg(feature = "std")]
+  fn write_to_with<W: io::Write>(&self, writer: &mut W, opts: &SerializeOptions) -> io::Result<()> {
+    let mut adapter = IoAdapter::new(writer);
+    self.write_element(0, true, Context::new(opts), &mut adapter).map_err(|_| adapter.into_error())
+  }
+
+  /// Serializes the element, the first line is indented only when `leading` is `true`.
+  ///
+  /// When caching is enabled in the context, the serialized output of the element is taken from the cache,
+  /// if present, otherwise the element is serialized and the output is stored in the cache.
+  fn write_element<W: fmt::Write>(&self, indent: usize, leading: bool, ctx: Context, w: &mut W) -> fmt::Result {
+    if ctx.cached && leading {
+      if let Some(output) = self.cache.get(indent) {
+        return w.write_str(&output);
+      }
+      let mut output = String::new();
+      self.write_uncached(indent, leading, ctx, &mut output)?;
+      w.write_str(&output)?;
+      self.cache.set(indent, output);
+      return Ok(());
+    }
+    self.write_uncached(indent, leading, ctx, w)
+  }
+
+  /// Serializes the element, ignoring its own cached output.
+  fn write_uncached<W: fmt::Write>(&self, indent: usize, leading: bool, ctx: Context, w: &mut W) -> fmt::Result {
+    let opts = ctx.opts;
+    let leading_indentation = if leading { opts.indentation(indent) } else { String::new() };
+    if self.text {
+      if let Some(content) = &self.content {
+        if ctx.inline {
+          return write_painted(w, content, ctx, COLOR_TEXT, ctx.raw_text);
+        }
+        for (i, line) in content.lines().enumerate() {
+          if i > 0 {
+            write!(w, "\n{}", opts.indentation(indent))?;
+          } else {
+            write!(w, "{}", leading_indentation)?;
+          }
+          write_painted(w, line, ctx, COLOR_TEXT, ctx.raw_text)?;
+        }
+      }
+      return Ok(());
+    }
+    write!(w, "{}<{}", leading_indentation, ctx.paint(COLOR_TAG, &self.name))?;
+    let foreign = ctx.foreign || is_foreign(&self.name);
+    let names: Vec<&str> = self.attributes.iter().map(|attribute| attribute.name.as_str()).collect();
+    for attribute in opts.attribute_order.sort(&names).into_iter().map(|position| &self.attributes[position]) {
+      let lowercase_name;
+      let name = if opts.lowercase_attr_names && !foreign {
+        lowercase_name = attribute.name.to_ascii_lowercase();
+        &lowercase_name
+      } else {
+        &attribute.name
+      };
+      let name = ctx.paint(COLOR_ATTR_NAME, name);
+      match &attribute.value {
+        AttributeValue::Str(value) => {
+          if value.contains('"') && !value.contains('\'') {
+            write!(w, " {}='", name)?;
+            write_painted(w, truncate_value(value, opts.max_attr_len), ctx, COLOR_ATTR_VALUE, false)?;
+            write!(w, "'")?;
+          } else {
+            // truncated after escaping, so the limit applies to the serialized value
+            let value = value.replace('"', "&quot;");
+            write!(w, r#" {}=""#, name)?;
+            write_painted(w, truncate_value(&value, opts.max_attr_len), ctx, COLOR_ATTR_VALUE, false)?;
+            write!(w, "\"")?;
+          }
+        }
+        AttributeValue::Int(value) => write!(w, r#" {}="{}""#, name, ctx.paint(COLOR_ATTR_VALUE, &value.to_string()))?,
+        AttributeValue::Bool => write!(w, " {}", name)?,
+      }
+    }
     if self.children.is_empty() {
       if let Some(content) = &self.content {
+        let guarded = guard_raw_text(&self.name, content);
+        let content: &str = &guarded;
+        let wrapped;
+        let block = !opts.minify && !ctx.inline;
+        let content = match opts.max_width {
+          Some(max_width) if block && !is_preformatted(&self.name) && content.lines().count() == 1 && content.chars().count() > max_width => {
+            wrapped = wrap_words(content, max_width);
+            // wrapped lines are kept only when written in block form
+            if wrapped.lines().count() >= opts.block_content_lines() {
+              &wrapped
+            } else {
+              content
+            }
+          }
+          _ => content,
+        };
         let line_count = content.lines().count();
-        if line_count > 1 {
-          let _ = write!(buffer, ">");
+        if block && line_count >= opts.block_content_lines() {
+          write!(w, ">")?;
           for line in content.lines() {
-            let _ = write!(buffer, "\n{}{}", indentation(indent + INDENT), line);
+            write!(w, "\n{}", opts.indentation(indent + INDENT))?;
+            write_painted(w, line, ctx, COLOR_TEXT, is_raw_text(&self.name))?;
+          }
+          if !ctx.omit_end {
+            write!(w, "\n{}</{}>", opts.indentation(indent), ctx.paint(COLOR_TAG, &self.name))?;
           }
-          let _ = write!(buffer, "\n{}</{}>", indentation(indent), self.name);
         } else {
-          let _ = write!(buffer, ">{}</{}>", content, self.name);
+          write!(w, ">")?;
+          write_painted(w, content, ctx, COLOR_TEXT, is_raw_text(&self.name))?;
+          if !ctx.omit_end {
+            write!(w, "</{}>", ctx.paint(COLOR_TAG, &self.name))?;
+          }
         }
       } else {
-        let _ = write!(buffer, "{}", if self.void { ">".to_string() } else { format!("></{}>", self.name) });
+        let void = self.void || opts.is_extra_void(&self.name);
+        write!(
+          w,
+          "{}",
+          if void {
+            opts.void_closing.as_str().to_string()
+          } else if ctx.omit_end {
+            ">".to_string()
+          } else {
+            format!("></{}>", ctx.paint(COLOR_TAG, &self.name))
+          }
+        )?;
+      }
+    } else if ctx.inline || self.is_inline() {
+      write!(w, ">")?;
+      if let Some(content) = &self.content {
+        write_painted(w, &guard_raw_text(&self.name, content), ctx, COLOR_TEXT, is_raw_text(&self.name))?;
+      }
+      for child in &self.children {
+        child.write_element(indent, false, ctx.within(self).inlined(), w)?;
+      }
+      if !ctx.omit_end {
+        write!(w, "</{}>", ctx.paint(COLOR_TAG, &self.name))?;
       }
     } else {
-      let _ = writeln!(buffer, ">");
+      write!(w, ">{}", opts.line_break())?;
+      // content (if any) precedes all children
+      if let Some(content) = self.content.as_ref().filter(|content| !content.is_empty()) {
+        if opts.minify {
+          write_painted(w, &guard_raw_text(&self.name, content), ctx, COLOR_TEXT, is_raw_text(&self.name))?;
+        } else {
+          for line in guard_raw_text(&self.name, content).lines() {
+            write!(w, "{}", opts.indentation(indent + INDENT))?;
+            write_painted(w, line, ctx, COLOR_TEXT, is_raw_text(&self.name))?;
+            writeln!(w)?;
+          }
+        }
+      }
       for (i, child) in self.children.iter().enumerate() {
         if i > 0 {
-          let _ = writeln!(buffer);
+          write!(w, "{}", opts.line_break())?;
         }
-        child.write(indent + INDENT, buffer);
+        let next = self.children.get(i + 1).map(|sibling| sibling.name.as_str());
+        let omit_end = opts.omit_optional_end_tags && is_end_tag_optional(&child.name, next, &self.name);
+        child.write_element(indent + INDENT, true, ctx.within(self).omitting_end(omit_end), w)?;
+      }
+      if !ctx.omit_end {
+        write!(w, "{}{}</{}>", opts.line_break(), opts.indentation(indent), ctx.paint(COLOR_TAG, &self.name))?;
       }
-      let _ = write!(buffer, "\n{}</{}>", indentation(indent), self.name);
     }
+    Ok(())
   }
 }
 
-fn indentation(indent: usize)->String {
-  " ".to_string().repeat(indent)
-}
\ No newline at end of file
+/// Context of serializing an element.
+#[derive(Clone, Copy)]
+struct Context<'a> {
+  /// Serialization options.
+  opts: &'a SerializeOptions,
+  /// Flag indicating if cached output of elements is used.
+  cached: bool,
+  /// Flag indicating if the element is placed within foreign content, like `<svg>` or `<math>`.
+  foreign: bool,
+  /// Flag indicating if the output is highlighted with ANSI colors.
+  highlight: bool,
+  /// Flag indicating if the end tag of the element is omitted.
+  omit_end: bool,
+  /// Flag indicating if the element is serialized inline, within an inline parent.
+  inline: bool,
+  /// Flag indicating if the element is placed within raw text element, like `<script>` or `<style>`.
+  raw_text: bool,
+}
+
+impl<'a> Context<'a> {
+  /// Creates a new serialization context with specified options.
+  fn new(opts: &'a SerializeOptions) -> Self {
+    Self {
+      opts,
+      cached: false,
+      foreign: false,
+      highlight: false,
+      omit_end: false,
+      inline: false,
+      raw_text: false,
+    }
+  }
+
+  /// Returns this context with highlighting enabled.
+  #[cfg(feature = "highlight")]
+  fn with_highlight(self) -> Self {
+    Self { highlight: true, ..self }
+  }
+
+  /// Returns the text wrapped in specified ANSI color when highlighting is enabled.
+  fn paint<'b>(&self, color: &str, text: &'b str) -> Cow<'b, str> {
+    if self.highlight {
+      Cow::Owned(format!("{}{}{}", color, text, COLOR_RESET))
+    } else {
+      Cow::Borrowed(text)
+    }
+  }
+
+  /// Returns this context with caching enabled.
+  fn with_cache(self) -> Self {
+    Self { cached: true, ..self }
+  }
+
+  /// Returns the context for serializing children of the specified parent element.
+  fn within(self, parent: &HtmlElement) -> Self {
+    Self {
+      foreign: !is_integration_point(&parent.name) && (self.foreign || is_foreign(&parent.name)),
+      omit_end: false,
+      raw_text: is_raw_text(&parent.name),
+      ..self
+    }
+  }
+
+  /// Returns this context with inline serialization enabled.
+  fn inlined(self) -> Self {
+    Self { inline: true, ..self }
+  }
+
+  /// Returns this context with omitting the end tag set as specified.
+  fn omitting_end(self, omit_end: bool) -> Self {
+    Self { omit_end, ..self }
+  }
+}
+
+/// Returns `true` for raw text elements, whose content is not decoded.
+fn is_raw_text(name: &str) -> bool {
+  matches!(name, "script" | "style")
+}
+
+/// Replaces characters not representable in the encoding with `CSS` escapes
+/// (when `css` is `true`) or with `JavaScript` escapes.
+#[cfg(feature = "encoding")]
+fn escape_unencodable<'a>(text: &'a str, encoding: &'static encoding_rs::Encoding, css: bool) -> Cow<'a, str> {
+  use fmt::Write;
+  if text.is_ascii() {
+    return Cow::Borrowed(text);
+  }
+  let mut escaped = String::with_capacity(text.len());
+  let mut buffer = [0; 4];
+  for ch in text.chars() {
+    let (_, _, unmappable) = encoding.encode(ch.encode_utf8(&mut buffer));
+    if !unmappable {
+      escaped.push(ch);
+    } else if css {
+      let _ = write!(escaped, "\\{:X} ", ch as u32);
+    } else {
+      for unit in ch.encode_utf16(&mut [0; 2]) {
+        let _ = write!(escaped, "\\u{:04X}", unit);
+      }
+    }
+  }
+  Cow::Owned(escaped)
+}
+
+/// Returns `true` for root elements of foreign content, where names are case-sensitive.
+fn is_foreign(name: &str) -> bool {
+  matches!(name, "svg" | "math")
+}
+
+/// Returns `true` for integration points of foreign content, whose children are `HTML` content again.
+fn is_integration_point(name: &str) -> bool {
+  matches!(name, "foreignObject" | "annotation-xml")
+}
+
+/// Writes the text content or attribute value according to serialization options.
+///
+/// Content of raw text elements (`raw` is `true`) is written as is, because
+/// character references are not decoded within `<script>` and `<style>` elements.
+fn write_text<W: fmt::Write>(w: &mut W, text: &str, opts: &SerializeOptions, raw: bool) -> fmt::Result {
+  if raw {
+    return w.write_str(text);
+  }
+  let text = match opts.entity_style {
+    EntityStyle::Named => Cow::Borrowed(text),
+    EntityStyle::Numeric => numeric_references(text),
+  };
+  let text = text.as_ref();
+  if opts.ascii_only {
+    write_ascii_only(w, text)
+  } else {
+    w.write_str(text)
+  }
+}
+
+/// Writes the text content or attribute value like [write_text], wrapped
+/// in specified ANSI color when highlighting is enabled in the context.
+fn write_painted<W: fmt::Write>(w: &mut W, text: &str, ctx: Context, color: &str, raw: bool) -> fmt::Result {
+  if ctx.highlight {
+    w.write_str(color)?;
+    write_text(w, text, ctx.opts, raw)?;
+    w.write_str(COLOR_RESET)
+  } else {
+    write_text(w, text, ctx.opts, raw)
+  }
+}
+
+/// Returns `true` when the end tag of the element with specified name may be omitted,
+/// considering the name of the next sibling (if any) and the name of the parent element.
+fn is_end_tag_optional(name: &str, next: Option<&str>, parent: &str) -> bool {
+  match name {
+    "li" => matches!(next, None | Some("li")),
+    "td" | "th" => matches!(next, None | Some("td" | "th")),
+    "tr" => matches!(next, None | Some("tr")),
+    "option" => matches!(next, None | Some("option" | "optgroup")),
+    "p" => match next {
+      None => !matches!(parent, "a" | "audio" | "del" | "ins" | "map" | "noscript" | "video"),
+      Some(next) => matches!(
+        next,
+        "address"
+          | "article"
+          | "aside"
+          | "blockquote"
+          | "details"
+          | "div"
+          | "dl"
+          | "fieldset"
+          | "figcaption"
+          | "figure"
+          | "footer"
+          | "form"
+          | "h1"
+          | "h2"
+          | "h3"
+          | "h4"
+          | "h5"
+          | "h6"
+          | "header"
+          | "hgroup"
+          | "hr"
+          | "main"
+          | "menu"
+          | "nav"
+          | "ol"
+          | "p"
+          | "pre"
+          | "section"
+          | "table"
+          | "ul"
+      ),
+    },
+    _ => false,
+  }
+}
+
+/// Truncates the attribute value to specified maximum number of characters,
+/// a character reference cut by the limit is removed entirely.
+fn truncate_value(value: &str, max_len: Option<usize>) -> &str {
+  match max_len.and_then(|max_len| value.char_indices().nth(max_len)) {
+    Some((end, _)) => {
+      let truncated = &value[..end];
+      match truncated.rfind('&') {
+        Some(start) if is_reference_cut(&value[start + 1..], end - start - 1) => &truncated[..start],
+        _ => truncated,
+      }
+    }
+    None => value,
+  }
+}
+
+/// Returns `true` when the text following `&` is a character reference,
+/// whose terminating `;` is at or after the specified cut position.
+fn is_reference_cut(text: &str, cut: usize) -> bool {
+  match text.find(|ch: char| !ch.is_ascii_alphanumeric() && ch != '#') {
+    Some(position) => position > 0 && position >= cut && text[position..].starts_with(';'),
+    None => false,
+  }
+}
+
+/// Returns `true` for phrasing elements, whose children are serialized inline by default.
+fn is_phrasing(name: &str) -> bool {
+  matches!(
+    name,
+    "a" | "abbr" | "b" | "cite" | "code" | "em" | "i" | "kbd" | "mark" | "q" | "s" | "samp" | "small" | "span" | "strong" | "sub" | "sup" | "time" | "u" | "var"
+  )
+}
+
+/// Returns `true` for obsolete presentational elements.
+fn is_obsolete(name: &str) -> bool {
+  matches!(name, "center" | "font" | "marquee" | "blink" | "big")
+}
+
+/// Returns `true` for obsolete presentational attributes.
+fn is_presentational(name: &str) -> bool {
+  matches!(name, "bgcolor" | "align")
+}
+
+/// Returns `true` for elements whose content must not be reformatted.
+fn is_preformatted(name: &str) -> bool {
+  matches!(name, "pre" | "textarea" | "script" | "style")
+}
+
+/// Guards the content of `<script>` and `<style>` raw text elements against
+/// prematurely closing the element, by breaking embedded closing tags like
+/// `</script` into `<\/script`. Content of other elements is returned unchanged.
+fn guard_raw_text<'a>(name: &str, content: &'a str) -> Cow<'a, str> {
+  if !is_raw_text(name) {
+    return Cow::Borrowed(content);
+  }
+  let closing = format!("</{}", name);
+  let lowercase = content.to_ascii_lowercase();
+  if !lowercase.contains(&closing) {
+    return Cow::Borrowed(content);
+  }
+  let mut guarded = String::with_capacity(content.len() + 1);
+  let mut last = 0;
+  for (index, _) in lowercase.match_indices(&closing) {
+    guarded.push_str(&content[last..index]);
+    guarded.push_str("<\\/");
+    last = index + 2;
+  }
+  guarded.push_str(&content[last..]);
+  Cow::Owned(guarded)
+}
+
+/// Wraps the text at word boundaries into lines not longer than specified width,
+/// words longer than the width are placed on separate lines.
+fn wrap_words(text: &str, width: usize) -> String {
+  let mut wrapped = String::new();
+  let mut line_width = 0;
+  for word in text.split_whitespace() {
+    let word_width = word.chars().count();
+    if line_width > 0 && line_width + 1 + word_width > width {
+      wrapped.push('\n');
+      line_width = 0;
+    } else if line_width > 0 {
+      wrapped.push(' ');
+      line_width += 1;
+    }
+    wrapped.push_str(word);
+    line_width += word_width;
+  }
+  wrapped
+}
+
+/// Appends whitespace separated class names to the value of the `class` attribute,
+/// skipping class names already present in this value.
+fn append_class_names(value: &mut String, class: &str) {
+  for class_name in class.split_whitespace() {
+    if !value.split_whitespace().any(|name| name == class_name) {
+      if !value.is_empty() {
+        value.push(' ');
+      }
+      value.push_str(class_name);
+    }
+  }
+}
@@ -12,9 +12,23 @@
  * SOFTWARE.
  */
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+mod diff;
+mod escape;
+mod form_builder;
 mod html_builder;
+mod options;
+mod validation;
 
-pub use html_builder::{HtmlDocument,HtmlElement, HeadingLevel};
+pub use diff::{Difference, DifferenceKind};
+pub use escape::{escape_attr, escape_text};
+pub use form_builder::FormBuilder;
+pub use html_builder::{ColSpec, HtmlAttribute, HtmlDocument,HtmlElement, HeadingLevel, MediaKind, TextDirection, render_opt};
+pub use options::{AttributeOrder, EntityStyle, SerializeOptions, VoidClosing};
+pub use validation::{BuildError, Severity, ValidationIssue};
 
 #[cfg(test)]
 mod tests;
\ No newline at end of file
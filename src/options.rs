@@ -0,0 +1,163 @@
+/*
+ * MIT license
+ *
+ * Copyright (c) 2023 Dariusz Depta
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Options controlling the serialization of `HTML` elements.
+
+use crate::html_builder::INDENT;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Closing style of void elements.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum VoidClosing {
+  /// Void elements are closed without a slash, e.g. `<br>`.
+  #[default]
+  Html,
+  /// Void elements are closed with a slash, e.g. `<br/>`.
+  Slash,
+  /// Void elements are closed with a space and a slash, e.g. `<br />`.
+  SpaceSlash,
+}
+
+impl VoidClosing {
+  /// Returns the text closing the start tag of a void element.
+  pub(crate) fn as_str(&self) -> &'static str {
+    match self {
+      VoidClosing::Html => ">",
+      VoidClosing::Slash => "/>",
+      VoidClosing::SpaceSlash => " />",
+    }
+  }
+}
+
+/// Style of character references of escaped special characters.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum EntityStyle {
+  /// Named character references, e.g. `&amp;` and `&lt;`.
+  #[default]
+  Named,
+  /// Numeric character references, e.g. `&#38;` and `&#60;`.
+  Numeric,
+}
+
+/// Order of attributes in serialized elements.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum AttributeOrder {
+  /// Attributes are serialized in the order they were set.
+  #[default]
+  Insertion,
+  /// Attributes are serialized in alphabetical order of their names.
+  Alphabetical,
+  /// Attributes with listed names are serialized first in the listed order,
+  /// followed by all remaining attributes in alphabetical order, e.g. `id`, `class`, then others.
+  Priority(Vec<String>),
+}
+
+impl AttributeOrder {
+  /// Sorts the attribute names according to this order, returns the positions of the names in sorted order.
+  pub(crate) fn sort(&self, names: &[&str]) -> Vec<usize> {
+    let mut positions: Vec<usize> = (0..names.len()).collect();
+    match self {
+      AttributeOrder::Insertion => {}
+      AttributeOrder::Alphabetical => positions.sort_by_key(|position| names[*position]),
+      AttributeOrder::Priority(priorities) => positions.sort_by_key(|position| {
+        let name = names[*position];
+        (priorities.iter().position(|priority| priority == name).unwrap_or(priorities.len()), name)
+      }),
+    }
+    positions
+  }
+}
+
+/// Options used when serializing `HTML` elements and documents.
+#[derive(Debug, Clone, Default)]
+pub struct SerializeOptions {
+  /// Names of additional elements serialized like void elements,
+  /// e.g. custom elements defined by web components.
+  pub extra_void: Vec<String>,
+  /// When `true`, all non-ASCII characters in text content and attribute values
+  /// are serialized as numeric character references, e.g. `é` as `&#233;`.
+  pub ascii_only: bool,
+  /// Maximum width of single-line content, longer content is wrapped at word boundaries
+  /// into multiple indented lines. Content of `<pre>`, `<textarea>`, `<script>`
+  /// and `<style>` elements is never wrapped, neither is content written inline
+  /// (when minifying or when wrapped lines are fewer than `block_content_lines`).
+  pub max_width: Option<usize>,
+  /// When `true`, attribute names of `HTML` elements are serialized in lowercase.
+  /// Attribute names within `<svg>` and `<math>` elements retain their case,
+  /// because they are case-sensitive, e.g. `viewBox`.
+  pub lowercase_attr_names: bool,
+  /// Closing style of void elements, by default void elements are closed like `<br>`.
+  pub void_closing: VoidClosing,
+  /// Minimum number of lines of content serialized in indented block form,
+  /// shorter content is serialized inline. By default content with at least
+  /// two lines is serialized in block form, `Some(1)` forces the block form always.
+  pub block_content_lines: Option<usize>,
+  /// When `true`, elements are serialized without indentation and line breaks between tags.
+  /// Line breaks within text content are preserved.
+  pub minify: bool,
+  /// Order of attributes in serialized elements, by default the order they were set.
+  pub attribute_order: AttributeOrder,
+  /// When `true`, optional end tags (like `</li>`, `</td>` or `</p>`) are omitted,
+  /// when the following sibling or the end of the parent element makes it unambiguous.
+  pub omit_optional_end_tags: bool,
+  /// Text used for each indentation level instead of two spaces, e.g. a tab.
+  pub indent_unit: Option<String>,
+  /// Maximum number of characters of serialized attribute values, longer values are truncated.
+  /// The limit is counted after escaping quotes, and values are never truncated
+  /// in the middle of a character reference like `&amp;` or `&quot;`.
+  pub max_attr_len: Option<usize>,
+  /// Style of character references of escaped special characters, by default named references.
+  pub entity_style: EntityStyle,
+}
+
+impl SerializeOptions {
+  /// Returns options using a single tab for each indentation level.
+  pub fn tabs() -> Self {
+    Self {
+      indent_unit: Some("\t".to_string()),
+      ..Default::default()
+    }
+  }
+
+  /// Returns `true` when the element with specified name is registered as an additional void element.
+  pub(crate) fn is_extra_void(&self, name: &str) -> bool {
+    self.extra_void.iter().any(|void_name| void_name == name)
+  }
+
+  /// Returns the indentation of a line indented with specified number of spaces, empty when minifying.
+  ///
+  /// When the indentation unit is set, each level of indentation is replaced with the unit.
+  pub(crate) fn indentation(&self, indent: usize) -> String {
+    match &self.indent_unit {
+      _ if self.minify => String::new(),
+      Some(unit) => unit.repeat(indent / INDENT) + &" ".repeat(indent % INDENT),
+      None => " ".repeat(indent),
+    }
+  }
+
+  /// Returns the line break placed between tags, empty when minifying.
+  pub(crate) fn line_break(&self) -> &'static str {
+    if self.minify {
+      ""
+    } else {
+      "\n"
+    }
+  }
+
+  /// Returns the minimum number of lines of content serialized in block form.
+  pub(crate) fn block_content_lines(&self) -> usize {
+    self.block_content_lines.unwrap_or(2)
+  }
+}
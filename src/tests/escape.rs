@@ -0,0 +1,63 @@
+/*
+ * MIT license
+ *
+ * Copyright (c) 2023 Dariusz Depta
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use crate::escape::{escape_attr, escape_text, numeric_references, percent_encode_url, write_ascii_only};
+use std::borrow::Cow;
+
+#[test]
+fn _0001() {
+  assert_eq!("a &amp; b &lt;c&gt; &quot;d&quot; &#39;e&#39;", escape_attr(r#"a & b <c> "d" 'e'"#));
+}
+
+#[test]
+fn _0002() {
+  assert_eq!(r#"a &amp; b &lt;c&gt; "d" 'e'"#, escape_text(r#"a & b <c> "d" 'e'"#));
+}
+
+#[test]
+fn _0003() {
+  let mut buffer = String::new();
+  write_ascii_only(&mut buffer, "naïve 😀 <ok>").unwrap();
+  assert_eq!("na&#239;ve &#128512; <ok>", buffer);
+}
+
+#[test]
+fn _0004() {
+  assert_eq!("https://example.com/a%20b/%C5%BC%C3%B3%C5%82w?q=x&y=%22z%22#top", percent_encode_url("https://example.com/a b/żółw?q=x&y=\"z\"#top"));
+  assert_eq!("/already%20encoded", percent_encode_url("/already%20encoded"));
+}
+
+#[test]
+fn _0005() {
+  assert!(matches!(escape_text("plain text 'quoted'"), Cow::Borrowed("plain text 'quoted'")));
+  assert!(matches!(escape_attr("plain-value"), Cow::Borrowed("plain-value")));
+  assert!(matches!(escape_text("a < b"), Cow::Owned(_)));
+  assert!(matches!(escape_attr("'a'"), Cow::Owned(_)));
+}
+
+#[test]
+fn _0006() {
+  assert_eq!("a &#38; b &#60;c&#62; &#34;d&#34; &#39;e&#39; &nbsp;", numeric_references("a &amp; b &lt;c&gt; &quot;d&quot; &#39;e&#39; &nbsp;"));
+  assert!(matches!(numeric_references("plain"), Cow::Borrowed("plain")));
+}
+
+#[test]
+fn _0007() {
+  let mut buffer = String::from("<p>");
+  buffer.push_str(&crate::escape_text("Clean text"));
+  buffer.push_str("</p>");
+  assert_eq!("<p>Clean text</p>", buffer);
+  assert!(matches!(crate::escape_text("Clean text"), Cow::Borrowed(_)));
+  assert_eq!("Tom &amp; &#39;Jerry&#39;", crate::escape_attr("Tom & 'Jerry'"));
+}
@@ -0,0 +1,44 @@
+/*
+ * MIT license
+ *
+ * Copyright (c) 2023 Dariusz Depta
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use crate::form_builder::FormBuilder;
+
+#[test]
+fn _0001() {
+  let form = FormBuilder::new("post", "/subscribe")
+    .text_field("email", "E-mail")
+    .checkbox("terms", "I accept terms & conditions")
+    .submit("Subscribe")
+    .build();
+  assert_eq!(
+    r#"<form method="post" action="/subscribe">
+  <div>
+    <label for="email">E-mail</label>
+    <input type="text" id="email" name="email">
+  </div>
+  <div>
+    <input type="checkbox" id="terms" name="terms">
+    <label for="terms">I accept terms &amp; conditions</label>
+  </div>
+  <button type="submit">Subscribe</button>
+</form>"#,
+    format!("{}", form)
+  );
+}
+
+#[test]
+fn _0002() {
+  let form = FormBuilder::new("get", "/search").build();
+  assert_eq!(r#"<form method="get" action="/search"></form>"#, format!("{}", form));
+}
@@ -1,4 +1,7 @@
-use crate::html_builder::HtmlElement;
+use crate::diff::DifferenceKind;
+use crate::html_builder::{render_opt, ColSpec, HtmlAttribute, HtmlDocument, HtmlElement, MediaKind, TextDirection};
+use crate::options::{AttributeOrder, EntityStyle, SerializeOptions, VoidClosing};
+use crate::validation::Severity;
 
 #[test]
 fn _0001() {
@@ -98,4 +101,1767 @@ body {
   root.add_child(body);
   // compare expected output with html document
   assert_eq!(expected, format!("{}", root));
-}
\ No newline at end of file
+}
+
+#[test]
+fn _0006() {
+  let element = HtmlElement::new_meta("description", r#"Tom & Jerry's "show""#);
+  assert_eq!(r#"<meta name="description" content="Tom &amp; Jerry&#39;s &quot;show&quot;">"#, format!("{}", element));
+}
+
+#[test]
+fn _0007() {
+  let element = HtmlElement::new_meta_property("og:title", "DMN <Model>");
+  assert_eq!(r#"<meta property="og:title" content="DMN &lt;Model&gt;">"#, format!("{}", element));
+}
+
+#[test]
+fn _0008() {
+  let mut element = HtmlElement::new("label");
+  element.set_content("Name:");
+  let mut input = HtmlElement::new_void("input");
+  input.set_attr("type", "text");
+  element.add_child(input);
+  assert_eq!(
+    r#"<label>
+  Name:
+  <input type="text">
+</label>"#,
+    format!("{}", element)
+  );
+}
+
+#[test]
+fn _0009() {
+  let mut element = HtmlElement::new("div");
+  element.set_content("first line\nsecond line");
+  element.add_child(HtmlElement::new("span"));
+  element.add_child(HtmlElement::new("span"));
+  assert_eq!(
+    r#"<div>
+  first line
+  second line
+  <span></span>
+  <span></span>
+</div>"#,
+    format!("{}", element)
+  );
+}
+
+#[test]
+fn _0010() {
+  let mut a = HtmlElement::new("div");
+  a.set_attr("id", "main");
+  a.set_class("card");
+  a.add_child(HtmlElement::new("span"));
+  let mut b = HtmlElement::new("div");
+  b.set_class("card");
+  b.set_attr("id", "main");
+  b.add_child(HtmlElement::new("span"));
+  assert_ne!(a, b);
+  assert!(a.eq_ignoring_attr_order(&b));
+  assert_eq!(a, a.clone());
+}
+
+#[test]
+fn _0011() {
+  let mut a = HtmlElement::new("div");
+  a.set_attr("id", "main");
+  let mut b = HtmlElement::new("div");
+  b.set_attr("id", "other");
+  assert!(!a.eq_ignoring_attr_order(&b));
+  let mut c = HtmlElement::new("div");
+  c.set_attr("id", "main");
+  c.add_child(HtmlElement::new("span"));
+  assert!(!a.eq_ignoring_attr_order(&c));
+}
+
+#[test]
+fn _0012() {
+  let mut head = HtmlElement::new("head");
+  let mut title = HtmlElement::new("title");
+  title.set_content("Custom");
+  head.add_child(title);
+  let body = HtmlElement::new("body");
+  let document = HtmlDocument::with_head_body("en", head, body);
+  assert_eq!(
+    r#"<!DOCTYPE html>
+<html lang="en" xmlns="http://www.w3.org/1999/xhtml">
+  <head>
+    <title>Custom</title>
+  </head>
+  <body></body>
+</html>"#,
+    format!("{}", document)
+  );
+}
+
+#[test]
+fn _0013() {
+  let mut document = HtmlDocument::with_head_body("ar-EG", HtmlElement::new("head"), HtmlElement::new("body"));
+  document.set_dir(TextDirection::Rtl);
+  assert_eq!(
+    r#"<!DOCTYPE html>
+<html lang="ar-EG" xmlns="http://www.w3.org/1999/xhtml" dir="rtl">
+  <head></head>
+  <body></body>
+</html>"#,
+    format!("{}", document)
+  );
+}
+
+#[test]
+fn _0014() {
+  for lang in ["", "en us", "en-", "-en", "e\"n", "toolongtag", "12-US"] {
+    let document = HtmlDocument::with_head_body(lang, HtmlElement::new("head"), HtmlElement::new("body"));
+    assert!(format!("{}", document).contains(r#"<html xmlns="http://www.w3.org/1999/xhtml">"#), "lang: {lang}");
+  }
+}
+
+#[test]
+fn _0015() {
+  let mut document = HtmlDocument::with_head_body("en", HtmlElement::new("head"), HtmlElement::new("body"));
+  document.set_dir(TextDirection::Auto);
+  document.set_dir(TextDirection::Ltr);
+  assert!(format!("{}", document).contains(r#"<html lang="en" xmlns="http://www.w3.org/1999/xhtml" dir="ltr">"#));
+}
+
+#[test]
+fn _0016() {
+  let element = HtmlElement::new("p").with_class("note").with_text("1 < 2 & 3 > 2");
+  assert_eq!(r#"<p class="note">1 &lt; 2 &amp; 3 &gt; 2</p>"#, format!("{}", element));
+}
+
+#[test]
+fn _0017() {
+  let element = HtmlElement::new("div")
+    .with_attr("id", "main")
+    .with_child(HtmlElement::new("p").with_raw("<b>bold</b>"));
+  assert_eq!(
+    r#"<div id="main">
+  <p><b>bold</b></p>
+</div>"#,
+    format!("{}", element)
+  );
+}
+
+#[test]
+fn _0018() {
+  let styles = ["body { margin: 0; }", "p { color: red; }"];
+  let stylesheet = styles.join("\n");
+  let expected = HtmlDocument::new("Title", "en", &styles, HtmlElement::new("body"));
+  let actual = HtmlDocument::new_with_stylesheet("Title", "en", &stylesheet, HtmlElement::new("body"));
+  assert_eq!(format!("{}", expected), format!("{}", actual));
+  assert!(format!("{}", actual).contains("<style>\n      body { margin: 0; }\n      p { color: red; }\n    </style>"));
+}
+
+#[test]
+fn _0019() {
+  let mut element = HtmlElement::new_void("input");
+  element.set_attr("type", "checkbox");
+  element.set_attr_int("tabindex", -1);
+  element.set_bool_attr("checked");
+  assert_eq!(r#"<input type="checkbox" tabindex="-1" checked>"#, format!("{}", element));
+}
+
+#[test]
+fn _0020() {
+  let mut nav = HtmlElement::new("nav");
+  nav.add_child(HtmlElement::new("a"));
+  let mut list = HtmlElement::new("ul");
+  list.add_child(HtmlElement::new("li").with_child(HtmlElement::new("a")));
+  list.add_child(HtmlElement::new("li").with_child(HtmlElement::new("a")));
+  nav.add_child(list);
+  let mut count = 0;
+  nav.walk(&mut |element| {
+    if element.name() == "a" {
+      count += 1;
+    }
+  });
+  assert_eq!(3, count);
+}
+
+#[test]
+fn _0021() {
+  let mut element = HtmlElement::new("div").with_child(HtmlElement::new("a")).with_child(HtmlElement::new("span").with_child(HtmlElement::new("a")));
+  let mut names = vec![];
+  element.walk(&mut |e| names.push(e.name().to_string()));
+  assert_eq!(vec!["div", "a", "span", "a"], names);
+  element.walk_mut(&mut |e| {
+    if e.name() == "a" {
+      e.set_attr("href", "#");
+    }
+  });
+  assert_eq!(
+    r##"<div>
+  <a href="#"></a>
+  <span><a href="#"></a></span>
+</div>"##,
+    format!("{}", element)
+  );
+}
+
+#[test]
+fn _0022() {
+  let mut element = HtmlElement::new("p");
+  element.add_children(HtmlElement::text_with_breaks("Line <1>\nLine & 2"));
+  assert_eq!(
+    r#"<p>
+  Line &lt;1&gt;
+  <br>
+  Line &amp; 2
+</p>"#,
+    format!("{}", element)
+  );
+}
+
+#[test]
+fn _0023() {
+  let mut element = HtmlElement::new("div");
+  element.set_attr("data-config", r#"{"name":"value","n":1}"#);
+  assert_eq!(r#"<div data-config='{"name":"value","n":1}'></div>"#, format!("{}", element));
+}
+
+#[test]
+fn _0024() {
+  let mut element = HtmlElement::new("div");
+  element.set_attr("data-text", r#"it's "quoted""#);
+  assert_eq!(r#"<div data-text="it's &quot;quoted&quot;"></div>"#, format!("{}", element));
+}
+
+#[test]
+fn _0025() {
+  let mut wrapper = HtmlElement::new_div(None);
+  wrapper.add_child(HtmlElement::new("h1"));
+  wrapper.add_child(HtmlElement::new("p"));
+  let mut parent = HtmlElement::new("body");
+  parent.add_children(wrapper.take_children());
+  assert_eq!("<div></div>", format!("{}", wrapper));
+  assert_eq!(
+    r#"<body>
+  <h1></h1>
+  <p></p>
+</body>"#,
+    format!("{}", parent)
+  );
+}
+
+#[test]
+fn _0026() {
+  let mut head = HtmlElement::new("head");
+  head.add_child(HtmlElement::new("style"));
+  head.add_child(HtmlElement::new_void("link").with_attr("rel", "icon"));
+  head.add_child(HtmlElement::new("title").with_text("Title"));
+  head.add_child(HtmlElement::new_meta("description", "Description"));
+  head.add_child(HtmlElement::new_void("meta").with_attr("charset", "UTF-8"));
+  let mut document = HtmlDocument::with_head_body("en", head, HtmlElement::new("body"));
+  document.sort_head();
+  assert_eq!(
+    r#"<!DOCTYPE html>
+<html lang="en" xmlns="http://www.w3.org/1999/xhtml">
+  <head>
+    <meta charset="UTF-8">
+    <title>Title</title>
+    <meta name="description" content="Description">
+    <link rel="icon">
+    <style></style>
+  </head>
+  <body></body>
+</html>"#,
+    format!("{}", document)
+  );
+}
+
+#[test]
+fn _0027() {
+  let mut document = HtmlDocument::new("Title", "en", &["body { margin: 0; }"], HtmlElement::new("body"));
+  document.add_css("h1 { color: red; }");
+  document.add_css("p { color: blue; }");
+  assert!(format!("{}", document).contains(
+    r#"    <style>
+      body { margin: 0; }
+      h1 { color: red; }
+      p { color: blue; }
+    </style>"#
+  ));
+}
+
+#[test]
+fn _0028() {
+  let mut document = HtmlDocument::with_head_body("en", HtmlElement::new("head"), HtmlElement::new("body"));
+  document.add_css("h1 { color: red; }");
+  assert!(format!("{}", document).contains(
+    r#"  <head>
+    <style>h1 { color: red; }</style>
+  </head>"#
+  ));
+}
+
+#[test]
+fn _0029() {
+  let mut element = HtmlElement::new("p");
+  element.append_content("Hello");
+  element.append_content(", World");
+  assert_eq!("<p>Hello, World</p>", format!("{}", element));
+}
+
+#[test]
+fn _0030() {
+  let element = HtmlElement::new("ul").with_child(HtmlElement::new("li"));
+  let mut buffer = "    <nav>".to_string();
+  element.write_no_leading_indent(4, &mut buffer);
+  assert_eq!(
+    r#"    <nav><ul>
+      <li></li>
+    </ul>"#,
+    buffer
+  );
+}
+
+#[test]
+fn _0031() {
+  assert_eq!("<span></span>", format!("{}", HtmlElement::new_span(None)));
+  assert_eq!(r#"<span class="badge"></span>"#, format!("{}", HtmlElement::new_span(Some("badge"))));
+}
+
+#[test]
+fn _0032() {
+  assert_eq!("<p>Fish &amp; Chips &lt;3</p>", format!("{}", HtmlElement::new_paragraph("Fish & Chips <3")));
+}
+
+/// Writer failing after accepting specified number of bytes.
+#[cfg(feature = "std")]
+struct FailingWriter {
+  remaining: usize,
+  written: Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for FailingWriter {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    if self.remaining == 0 {
+      return Err(std::io::Error::other("writer failed"));
+    }
+    let count = buf.len().min(self.remaining);
+    self.remaining -= count;
+    self.written.extend_from_slice(&buf[..count]);
+    Ok(count)
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn _0033() {
+  let element = HtmlElement::new("ul").with_child(HtmlElement::new("li")).with_child(HtmlElement::new("li"));
+  let mut buffer = vec![];
+  element.write_to(&mut buffer).unwrap();
+  assert_eq!(format!("{}", element).as_bytes(), buffer.as_slice());
+  let document = HtmlDocument::with_head_body("en", HtmlElement::new("head"), element);
+  let mut buffer = vec![];
+  document.write_to(&mut buffer).unwrap();
+  assert_eq!(format!("{}", document).as_bytes(), buffer.as_slice());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn _0034() {
+  let element = HtmlElement::new("ul").with_child(HtmlElement::new("li")).with_child(HtmlElement::new("li"));
+  let mut writer = FailingWriter { remaining: 10, written: vec![] };
+  let error = element.write_to(&mut writer).unwrap_err();
+  assert_eq!("writer failed", error.to_string());
+  assert_eq!(b"<ul>\n  <li", writer.written.as_slice());
+}
+
+#[test]
+fn _0035() {
+  let mut element = HtmlElement::new("script");
+  element.set_cdata("if (a < b && c > d) { run(); }");
+  assert_eq!("<script><![CDATA[if (a < b && c > d) { run(); }]]></script>", format!("{}", element));
+}
+
+#[test]
+fn _0036() {
+  let mut element = HtmlElement::new("script");
+  element.set_cdata("let s = 'a]]>b';");
+  assert_eq!("<script><![CDATA[let s = 'a]]]]><![CDATA[>b';]]></script>", format!("{}", element));
+}
+
+#[test]
+fn _0037() {
+  let mut element = HtmlElement::new("a");
+  element.set_attr_opt("href", Some("https://example.com"));
+  element.set_attr_opt::<&str>("title", None);
+  let element = element.with_attr_opt("target", Some("_blank")).with_attr_opt::<u32>("tabindex", None);
+  assert_eq!(r#"<a href="https://example.com" target="_blank"></a>"#, format!("{}", element));
+}
+
+#[test]
+fn _0038() {
+  let mut element = HtmlElement::new("div");
+  element.add_class("foo bar");
+  element.add_class("  bar\tbaz ");
+  assert!(element.has_class("foo"));
+  assert!(element.has_class("bar"));
+  assert!(element.has_class("baz"));
+  assert!(!element.has_class("foo bar"));
+  assert_eq!(r#"<div class="foo bar baz"></div>"#, format!("{}", element));
+}
+
+#[test]
+fn _0039() {
+  let mut element = HtmlElement::new("div");
+  element.add_class("   ");
+  assert!(!element.has_class(""));
+  assert_eq!("<div></div>", format!("{}", element));
+}
+
+#[test]
+fn _0040() {
+  let document = HtmlDocument::with_head_body("en", HtmlElement::new("head"), HtmlElement::new("body"));
+  let mut buffer = String::new();
+  document.write_at(4, &mut buffer);
+  assert_eq!(
+    r#"    <!DOCTYPE html>
+    <html lang="en" xmlns="http://www.w3.org/1999/xhtml">
+      <head></head>
+      <body></body>
+    </html>"#,
+    buffer
+  );
+}
+
+#[test]
+fn _0041() {
+  let mut element = HtmlElement::new("div");
+  element.add_child(HtmlElement::new("my-icon").with_attr("name", "star"));
+  element.add_child(HtmlElement::new("my-label"));
+  let opts = SerializeOptions {
+    extra_void: vec!["my-icon".to_string()],
+    ..Default::default()
+  };
+  let mut buffer = String::new();
+  element.write_with(0, &opts, &mut buffer);
+  assert_eq!(
+    r#"<div>
+  <my-icon name="star">
+  <my-label></my-label>
+</div>"#,
+    buffer
+  );
+}
+
+#[test]
+fn _0042() {
+  let element = HtmlElement::new("form")
+    .with_child(HtmlElement::new_void("input").with_attr("type", "text"))
+    .with_child(HtmlElement::new_void("input").with_attr("type", "email").with_attr("required", "required"))
+    .with_child(HtmlElement::new_void("input").with_attr("required", "required"));
+  let found = element.find_child(|child| child.has_attr("required")).unwrap();
+  assert_eq!(r#"<input type="email" required="required">"#, format!("{}", found));
+  assert!(element.find_child(|child| child.name() == "button").is_none());
+}
+
+#[test]
+fn _0043() {
+  let element = HtmlElement::new("div")
+    .with_child(HtmlElement::new("p").with_child(HtmlElement::new("a").with_attr("href", "first")))
+    .with_child(HtmlElement::new("a").with_attr("href", "second"));
+  let found = element.find_descendant(|e| e.name() == "a").unwrap();
+  assert_eq!(r#"<a href="first"></a>"#, format!("{}", found));
+  assert!(element.find_descendant(|e| e.name() == "div").is_none());
+}
+
+#[test]
+fn _0044() {
+  let mut table = HtmlElement::new("table");
+  let row = table.add_child(HtmlElement::new("tr"));
+  let cell = row.add_child(HtmlElement::new("td"));
+  cell.set_content("cell");
+  assert_eq!(
+    r#"<table>
+  <tr>
+    <td>cell</td>
+  </tr>
+</table>"#,
+    format!("{}", table)
+  );
+}
+
+#[test]
+fn _0045() {
+  let element = HtmlElement::new("p").with_attr("title", "Café").with_text("Crème brûlée");
+  assert_eq!(r#"<p title="Café">Crème brûlée</p>"#, format!("{}", element));
+  let opts = SerializeOptions {
+    ascii_only: true,
+    ..Default::default()
+  };
+  let mut buffer = String::new();
+  element.write_with(0, &opts, &mut buffer);
+  assert_eq!(r#"<p title="Caf&#233;">Cr&#232;me br&#251;l&#233;e</p>"#, buffer);
+}
+
+#[test]
+fn _0046() {
+  let mut br = HtmlElement::new_void("br");
+  br.set_content("line");
+  let element = HtmlElement::new("p").with_child(HtmlElement::new_void("hr")).with_child(br);
+  let issues = element.validate();
+  assert_eq!(1, issues.len());
+  assert_eq!(Severity::Error, issues[0].severity());
+  assert_eq!("br", issues[0].element());
+  assert_eq!("error: <br> void element has content", issues[0].to_string());
+  assert!(HtmlElement::new("p").with_text("text").validate().is_empty());
+}
+
+#[test]
+fn _0047() {
+  let mut img = HtmlElement::new_void("img");
+  img.children_mut().push(HtmlElement::new("span"));
+  let issues = img.validate();
+  assert_eq!(1, issues.len());
+  assert_eq!("error: <img> void element has children", issues[0].to_string());
+}
+
+#[test]
+fn _0048() {
+  let element = HtmlElement::new("div")
+    .with_attr("id", "target")
+    .with_child(HtmlElement::new_paragraph("first"))
+    .with_child(HtmlElement::new("ul").with_child(HtmlElement::new("li")));
+  let full = format!("{}", element);
+  let lines = full.lines().collect::<Vec<_>>();
+  let expected = lines[1..lines.len() - 1].iter().map(|line| &line[2..]).collect::<Vec<_>>().join("\n");
+  assert_eq!(expected, element.inner_html());
+  assert_eq!("<p>first</p>\n<ul>\n  <li></li>\n</ul>", element.inner_html());
+}
+
+#[test]
+fn _0049() {
+  assert_eq!("", HtmlElement::new("div").inner_html());
+  assert_eq!("text &amp; more", HtmlElement::new("p").with_text("text & more").inner_html());
+}
+
+#[test]
+fn _0050() {
+  let mut element = HtmlElement::new("article").with_child(HtmlElement::new("p"));
+  element.prepend_child(HtmlElement::new("header")).set_content("Title");
+  let element = element.with_prepended_child(HtmlElement::new("nav"));
+  assert_eq!(
+    r#"<article>
+  <nav></nav>
+  <header>Title</header>
+  <p></p>
+</article>"#,
+    format!("{}", element)
+  );
+}
+
+#[test]
+fn _0051() {
+  let mut element = HtmlElement::new("ul").with_child(HtmlElement::new("li").with_text("a")).with_child(HtmlElement::new("li").with_text("c"));
+  element.insert_child(1, HtmlElement::new("li").with_text("b"));
+  assert_eq!("<li>b</li>", format!("{}", element.remove_child(1).unwrap()));
+  assert!(element.remove_child(2).is_none());
+  assert_eq!("<li>a</li>\n<li>c</li>", element.inner_html());
+}
+
+#[test]
+fn _0052() {
+  let element = HtmlElement::new_details("Q & A", HtmlElement::new_paragraph("Answer"), true);
+  assert_eq!(
+    r#"<details open>
+  <summary>Q &amp; A</summary>
+  <p>Answer</p>
+</details>"#,
+    format!("{}", element)
+  );
+  let element = HtmlElement::new_details("More", HtmlElement::new_div(None), false);
+  assert!(format!("{}", element).starts_with("<details>\n"));
+}
+
+#[test]
+fn _0053() {
+  let mut document = HtmlDocument::new("Title", "en", &[], HtmlElement::new("body"));
+  document.add_viewport("width=device-width, initial-scale=1");
+  let output = format!("{}", document);
+  assert!(output.contains(
+    r#"    <meta charset="UTF-8">
+    <title>Title</title>
+    <meta name="viewport" content="width=device-width, initial-scale=1">"#
+  ));
+  document.sort_head();
+  assert_eq!(output, format!("{}", document));
+}
+
+#[test]
+fn _0054() {
+  let mut element = HtmlElement::new("td");
+  for (name, value) in [("colspan", "2"), ("class", "total")] {
+    element.add_attribute(HtmlAttribute::new(name, value));
+  }
+  element.set_attr_int("rowspan", 3);
+  element.set_bool_attr("hidden");
+  let attributes = element.attributes().iter().map(|a| format!("{}={}", a.name(), a.value())).collect::<Vec<_>>();
+  assert_eq!(vec!["colspan=2", "class=total", "rowspan=3", "hidden="], attributes);
+  assert_eq!(r#"<td colspan="2" class="total" rowspan="3" hidden></td>"#, format!("{}", element));
+}
+
+#[test]
+fn _0055() {
+  let badge = Some(HtmlElement::new_span(Some("badge")));
+  let none = None;
+  assert_eq!(r#"Item <span class="badge"></span>"#, format!("Item {}", render_opt(&badge)));
+  assert_eq!("Item ", format!("Item {}", render_opt(&none)));
+}
+
+#[test]
+fn _0056() {
+  let mut element = HtmlElement::new("button").with_attr("type", "button").with_class("btn primary");
+  let overrides = HtmlElement::new("button").with_class("primary large").with_attr("type", "submit").with_attr("id", "send");
+  element.merge_attrs(&overrides);
+  assert_eq!(r#"<button type="submit" class="btn primary large" id="send"></button>"#, format!("{}", element));
+}
+
+#[test]
+fn _0057() {
+  let line = line!() + 1;
+  let element = HtmlElement::new("div").with_source_location();
+  assert_eq!(format!(r#"<div data-src="{}:{}:41"></div>"#, file!(), line), format!("{}", element));
+  let mut element = HtmlElement::new("p");
+  let line = line!() + 1;
+  element.set_source_location();
+  assert_eq!(format!(r#"<p data-src="{}:{}:11"></p>"#, file!(), line), format!("{}", element));
+}
+
+#[test]
+fn _0058() {
+  let text = "The quick brown fox jumps over the lazy dog and keeps running far away.";
+  let element = HtmlElement::new("div").with_child(HtmlElement::new_paragraph(text)).with_child(HtmlElement::new("pre").with_text(text));
+  let opts = SerializeOptions {
+    max_width: Some(40),
+    ..Default::default()
+  };
+  let mut buffer = String::new();
+  element.write_with(0, &opts, &mut buffer);
+  assert_eq!(
+    r#"<div>
+  <p>
+    The quick brown fox jumps over the lazy
+    dog and keeps running far away.
+  </p>
+  <pre>The quick brown fox jumps over the lazy dog and keeps running far away.</pre>
+</div>"#,
+    buffer
+  );
+}
+
+#[test]
+fn _0059() {
+  let body = HtmlElement::new("body")
+    .with_child(HtmlElement::new_header())
+    .with_child(HtmlElement::new_nav())
+    .with_child(HtmlElement::new_main().with_child(HtmlElement::new_section(Some("Results & summary"))).with_child(HtmlElement::new_section(None)))
+    .with_child(HtmlElement::new_footer());
+  assert_eq!(
+    r#"<body>
+  <header></header>
+  <nav></nav>
+  <main>
+    <section aria-label="Results &amp; summary"></section>
+    <section></section>
+  </main>
+  <footer></footer>
+</body>"#,
+    format!("{}", body)
+  );
+}
+
+#[test]
+fn _0060() {
+  let document = HtmlDocument::new("Title", "en", &[], HtmlElement::new("body"));
+  assert!(!format!("{}", document).contains("<style"));
+  let mut document = HtmlDocument::new("Title", "en", &[], HtmlElement::new("body"));
+  document.add_css("p { margin: 0; }");
+  assert!(format!("{}", document).contains("<style>p { margin: 0; }</style>"));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "void element <img> can not have children")]
+fn _0061() {
+  let mut img = HtmlElement::new_void("img");
+  img.add_child(HtmlElement::new("span"));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "void element <br> can not have children")]
+fn _0062() {
+  let mut br = HtmlElement::new_void("br");
+  br.prepend_child(HtmlElement::new("span"));
+}
+
+#[test]
+fn _0063() {
+  let mut element = HtmlElement::new("a");
+  element.set_url_attr("href", "/docs/user guide/café.html");
+  assert_eq!(r#"<a href="/docs/user%20guide/caf%C3%A9.html"></a>"#, format!("{}", element));
+}
+
+#[test]
+fn _0064() {
+  let mut table = HtmlElement::new("table");
+  for row in 0..3 {
+    let tr = table.add_child(HtmlElement::new("tr"));
+    for column in 0..3 {
+      tr.add_child(HtmlElement::new("td").with_text(&format!("{row}:{column}")));
+    }
+  }
+  let mut buffer = String::new();
+  table.write_cached(0, &mut buffer);
+  assert_eq!(format!("{}", table), buffer);
+  let mut buffer = String::new();
+  table.write_cached(0, &mut buffer);
+  assert_eq!(format!("{}", table), buffer);
+  // modify a single cell and render again
+  table.child_mut(1).and_then(|tr| tr.child_mut(1)).unwrap().set_class("selected");
+  let mut buffer = String::new();
+  table.write_cached(0, &mut buffer);
+  assert!(buffer.contains(r#"<td class="selected">1:1</td>"#));
+  assert_eq!(format!("{}", table), buffer);
+}
+
+#[test]
+fn _0065() {
+  let mut element = HtmlElement::new("ul");
+  let item = element.add_child(HtmlElement::new("li"));
+  item.set_content("first");
+  let mut buffer = String::new();
+  element.write_cached(2, &mut buffer);
+  assert_eq!("  <ul>\n    <li>first</li>\n  </ul>", buffer);
+  element.add_child(HtmlElement::new("li")).set_content("second");
+  let mut buffer = String::new();
+  element.write_cached(2, &mut buffer);
+  assert_eq!("  <ul>\n    <li>first</li>\n    <li>second</li>\n  </ul>", buffer);
+  assert_eq!(element, element.clone());
+}
+
+#[test]
+fn _0066() {
+  let mut element = HtmlElement::new("abbr");
+  element.set_title(r#"The "HyperText" Markup Language"#);
+  assert_eq!(r#"<abbr title="The &quot;HyperText&quot; Markup Language"></abbr>"#, format!("{}", element));
+  let element = HtmlElement::new("span").with_title("Tom & Jerry's");
+  assert_eq!(r#"<span title="Tom &amp; Jerry&#39;s"></span>"#, format!("{}", element));
+}
+
+#[test]
+fn _0067() {
+  let sections = vec![HtmlElement::new_header(), HtmlElement::new_main(), HtmlElement::new_footer()];
+  let document = HtmlDocument::new_from_sections("Title", "en", &[], sections);
+  assert!(format!("{}", document).ends_with(
+    r#"  <body>
+    <header></header>
+    <main></main>
+    <footer></footer>
+  </body>
+</html>"#
+  ));
+}
+
+#[test]
+fn _0068() {
+  let mut element = HtmlElement::new("div");
+  assert!(element.is_empty());
+  element.set_class("card");
+  assert!(!element.is_empty());
+  assert!(!HtmlElement::new("p").with_text("text").is_empty());
+  assert!(!HtmlElement::new("div").with_child(HtmlElement::new("p")).is_empty());
+}
+
+#[test]
+fn _0069() {
+  let svg = HtmlElement::new("svg")
+    .with_attr("viewBox", "0 0 10 10")
+    .with_child(HtmlElement::new("linearGradient").with_attr("gradientUnits", "userSpaceOnUse"));
+  let element = HtmlElement::new("div").with_attr("onClick", "go()").with_child(svg);
+  let opts = SerializeOptions {
+    lowercase_attr_names: true,
+    ..Default::default()
+  };
+  let mut buffer = String::new();
+  element.write_with(0, &opts, &mut buffer);
+  assert_eq!(
+    r#"<div onclick="go()">
+  <svg viewBox="0 0 10 10">
+    <linearGradient gradientUnits="userSpaceOnUse"></linearGradient>
+  </svg>
+</div>"#,
+    buffer
+  );
+}
+
+#[test]
+fn _0070() {
+  let element = HtmlElement::new("ul").with_child(HtmlElement::new("li").with_text("zażółć"));
+  assert_eq!(element.to_string().into_bytes(), element.to_bytes());
+  let document = HtmlDocument::new("Title", "pl", &["p {}"], HtmlElement::new("body").with_child(element));
+  assert_eq!(document.to_string().into_bytes(), document.to_bytes());
+}
+
+#[test]
+fn _0071() {
+  let mut element = HtmlElement::new_div(Some("card"));
+  element.add_class("highlight");
+  assert_eq!(r#"<div class="card highlight"></div>"#, format!("{}", element));
+  element.set_class("plain");
+  element.set_attr("id", "x");
+  element.set_class("replaced");
+  assert_eq!(r#"<div class="replaced" id="x"></div>"#, format!("{}", element));
+  element.add_attribute(HtmlAttribute::new("class", "duplicate"));
+  element.set_class("single");
+  assert_eq!(r#"<div class="single" id="x"></div>"#, format!("{}", element));
+}
+
+#[test]
+fn _0072() {
+  let body = HtmlElement::new("body").with_child(HtmlElement::new_div(None).with_attr("id", "main"));
+  assert!(HtmlDocument::try_new("Title", "en-US", &[], body).is_ok());
+}
+
+#[test]
+fn _0073() {
+  let body = HtmlElement::new("body")
+    .with_child(HtmlElement::new("my element"))
+    .with_child(HtmlElement::new_div(None).with_attr("id", "main"))
+    .with_child(HtmlElement::new_span(None).with_attr("id", "main"));
+  let error = HtmlDocument::try_new("Title", "en US", &[], body).unwrap_err();
+  assert_eq!(3, error.issues().len());
+  assert_eq!(
+    r#"building document failed
+  error: <html> has invalid lang 'en US'
+  error: <my element> has invalid name
+  error: <span> has duplicated id 'main'"#,
+    error.to_string()
+  );
+}
+
+#[test]
+fn _0074() {
+  let element = HtmlElement::new_void("br");
+  for (void_closing, expected) in [(VoidClosing::Html, "<br>"), (VoidClosing::Slash, "<br/>"), (VoidClosing::SpaceSlash, "<br />")] {
+    let opts = SerializeOptions { void_closing, ..Default::default() };
+    let mut buffer = String::new();
+    element.write_with(0, &opts, &mut buffer);
+    assert_eq!(expected, buffer);
+  }
+}
+
+#[test]
+fn _0075() {
+  let mut element = HtmlElement::new("ul").with_child(HtmlElement::new("li").with_text("3"));
+  element.prepend_children(["1", "2"].into_iter().map(|text| HtmlElement::new("li").with_text(text)));
+  element.append_children(Some(HtmlElement::new("li").with_text("4")));
+  let mut buffer = String::new();
+  element.write(0, &mut buffer);
+  assert_eq!("<ul>\n  <li>1</li>\n  <li>2</li>\n  <li>3</li>\n  <li>4</li>\n</ul>", buffer);
+}
+
+#[test]
+fn _0076() {
+  let element = HtmlElement::new("body").with_child(HtmlElement::new("script").with_attr("src", "app.js")).with_child(HtmlElement::new_paragraph("Text"));
+  let mut buffer = String::new();
+  element.write_with_hook(0, &mut buffer, |e| {
+    if e.name() == "script" {
+      e.set_attr("nonce", "r4nd0m");
+    }
+  });
+  assert_eq!("<body>\n  <script src=\"app.js\" nonce=\"r4nd0m\"></script>\n  <p>Text</p>\n</body>", buffer);
+  assert!(!element.find_descendant(|e| e.name() == "script").unwrap().has_attr("nonce"));
+}
+
+#[test]
+fn _0077() {
+  let mut element = HtmlElement::new("p");
+  element.set_content("Line 1\nLine 2");
+  let mut buffer = String::new();
+  element.write_with(0, &SerializeOptions::default(), &mut buffer);
+  assert_eq!("<p>\n  Line 1\n  Line 2\n</p>", buffer);
+  let mut buffer = String::new();
+  element.write_with(0, &SerializeOptions { block_content_lines: Some(3), ..Default::default() }, &mut buffer);
+  assert_eq!("<p>Line 1\nLine 2</p>", buffer);
+  let mut buffer = String::new();
+  HtmlElement::new_paragraph("Line").write_with(0, &SerializeOptions { block_content_lines: Some(1), ..Default::default() }, &mut buffer);
+  assert_eq!("<p>\n  Line\n</p>", buffer);
+}
+
+#[test]
+fn _0078() {
+  let element = HtmlElement::new_breadcrumb(&[("Home", Some("/")), ("Docs & Guides", Some("/docs")), ("Intro", None)]);
+  let mut buffer = String::new();
+  element.write(0, &mut buffer);
+  assert_eq!(
+    r#"<nav aria-label="breadcrumb">
+  <ol>
+    <li>
+      <a href="/">Home</a>
+    </li>
+    <li>
+      <a href="/docs">Docs &amp; Guides</a>
+    </li>
+    <li aria-current="page">Intro</li>
+  </ol>
+</nav>"#,
+    buffer
+  );
+}
+
+#[test]
+fn _0079() {
+  let mut element = HtmlElement::new("script");
+  element.set_content(r#"if (a < b) { document.write("</SCRIPT><script>x()</script>"); }"#);
+  let mut buffer = String::new();
+  element.write(0, &mut buffer);
+  assert_eq!(r#"<script>if (a < b) { document.write("<\/SCRIPT><script>x()<\/script>"); }</script>"#, buffer);
+}
+
+#[test]
+fn _0080() {
+  let element = HtmlElement::new("ul").with_child(HtmlElement::new("li").with_text("One")).with_child(HtmlElement::new("li").with_attr("class", "last").with_text("Two"));
+  let mut buffer = String::from("<!-- list -->\n");
+  element.write_into(&mut buffer);
+  element.write_into(&mut buffer);
+  let expected = "<ul>\n  <li>One</li>\n  <li class=\"last\">Two</li>\n</ul>";
+  assert_eq!(format!("<!-- list -->\n{0}{0}", expected), buffer);
+  assert!(element.estimated_len() >= expected.len());
+  assert!(element.estimated_len() < 2 * expected.len());
+}
+
+#[test]
+fn _0081() {
+  let element = HtmlElement::void("img").with_attr("src", "logo.png").with_attr("alt", "Logo");
+  let mut buffer = String::new();
+  element.write(0, &mut buffer);
+  assert_eq!(r#"<img src="logo.png" alt="Logo">"#, buffer);
+  assert_eq!(HtmlElement::new_void("img"), HtmlElement::void("img"));
+}
+
+#[test]
+fn _0082() {
+  let tree = |class: &str| {
+    HtmlElement::new("body")
+      .with_child(HtmlElement::new_paragraph("Intro"))
+      .with_child(HtmlElement::new_div(None).with_child(HtmlElement::new_span(Some(class)).with_text("Text")))
+  };
+  assert!(tree("a").diff(&tree("a")).is_empty());
+  let differences = tree("a").diff(&tree("b"));
+  assert_eq!(1, differences.len());
+  assert_eq!("body/div[1]/span[0]", differences[0].path());
+  assert_eq!(
+    &DifferenceKind::AttributeChanged {
+      name: "class".to_string(),
+      old: "a".to_string(),
+      new: "b".to_string()
+    },
+    differences[0].kind()
+  );
+  assert_eq!("body/div[1]/span[0]: attribute 'class' changed from 'a' to 'b'", differences[0].to_string());
+}
+
+#[test]
+fn _0083() {
+  let element = HtmlElement::new_picture(
+    &[("(min-width: 800px)", "large.webp 1x, large@2x.webp 2x"), ("(min-width: 400px)", "medium.webp")],
+    "small.jpg",
+    "Cats & dogs",
+  );
+  let mut buffer = String::new();
+  element.write(0, &mut buffer);
+  assert_eq!(
+    r#"<picture>
+  <source media="(min-width: 800px)" srcset="large.webp 1x, large@2x.webp 2x">
+  <source media="(min-width: 400px)" srcset="medium.webp">
+  <img src="small.jpg" alt="Cats &amp; dogs">
+</picture>"#,
+    buffer
+  );
+}
+
+#[test]
+fn _0084() {
+  let body = HtmlElement::new("body").with_child(HtmlElement::new_div(None).with_child(HtmlElement::new_paragraph("Hello")));
+  let head = HtmlElement::new("head").with_child(HtmlElement::new("title").with_text("Title"));
+  let document = HtmlDocument::with_head_body("en", head, body);
+  let mut buffer = String::new();
+  document.write_with(&SerializeOptions { minify: true, ..Default::default() }, &mut buffer);
+  assert_eq!(
+    r#"<!DOCTYPE html><html lang="en" xmlns="http://www.w3.org/1999/xhtml"><head><title>Title</title></head><body><div><p>Hello</p></div></body></html>"#,
+    buffer
+  );
+  let mut buffer = String::new();
+  document.write_with(&SerializeOptions::default(), &mut buffer);
+  assert_eq!(document.to_string(), buffer);
+}
+
+#[test]
+fn _0085() {
+  let mut element = HtmlElement::new("a").with_attr_if(true, "href", "/home").with_attr_if(false, "target", "_blank");
+  element.set_attr_if(false, "rel", "noopener");
+  element.set_attr_if(true, "title", "Home");
+  let mut buffer = String::new();
+  element.write(0, &mut buffer);
+  assert_eq!(r#"<a href="/home" title="Home"></a>"#, buffer);
+}
+
+#[test]
+fn _0086() {
+  let table = HtmlElement::new("table").with_child(HtmlElement::new("tr").with_child(HtmlElement::new("td").with_text("1")));
+  let element = table.wrap("div", Some("table-responsive"));
+  let mut buffer = String::new();
+  element.write(0, &mut buffer);
+  assert_eq!(
+    r#"<div class="table-responsive">
+  <table>
+    <tr>
+      <td>1</td>
+    </tr>
+  </table>
+</div>"#,
+    buffer
+  );
+}
+
+#[test]
+fn _0087() {
+  let wrapper = HtmlElement::new_div(Some("redundant")).with_child(HtmlElement::new_paragraph("One")).with_child(HtmlElement::new_paragraph("Two"));
+  let children = wrapper.unwrap_into();
+  assert_eq!(2, children.len());
+  let mut parent = HtmlElement::new("section");
+  parent.append_children(children);
+  let mut buffer = String::new();
+  parent.write(0, &mut buffer);
+  assert_eq!("<section>\n  <p>One</p>\n  <p>Two</p>\n</section>", buffer);
+}
+
+#[test]
+fn _0088() {
+  let mut element = HtmlElement::new_div(None);
+  element.set_hidden(true);
+  element.set_draggable(true);
+  element.set_contenteditable(false);
+  let mut buffer = String::new();
+  element.write(0, &mut buffer);
+  assert_eq!(r#"<div hidden draggable="true" contenteditable="false"></div>"#, buffer);
+  element.set_hidden(false);
+  element.set_draggable(false);
+  element.set_contenteditable(true);
+  let mut buffer = String::new();
+  element.write(0, &mut buffer);
+  assert_eq!(r#"<div draggable="false" contenteditable="true"></div>"#, buffer);
+}
+
+#[test]
+fn _0089() {
+  let root = HtmlElement::new("definitions")
+    .with_attr("xmlns", "https://www.omg.org/spec/DMN/20191111/MODEL/")
+    .with_attr("xmlns:dmndi", "https://www.omg.org/spec/DMN/20191111/DMNDI/")
+    .with_attr("id", "model")
+    .with_child(HtmlElement::new("decision").with_attr("id", "d1"));
+  let document = HtmlDocument::from_root(root, false);
+  assert_eq!(
+    r#"<definitions xmlns="https://www.omg.org/spec/DMN/20191111/MODEL/" xmlns:dmndi="https://www.omg.org/spec/DMN/20191111/DMNDI/" id="model">
+  <decision id="d1"></decision>
+</definitions>"#,
+    document.to_string()
+  );
+  let document = HtmlDocument::from_root(HtmlElement::new("html"), true);
+  assert_eq!("<!DOCTYPE html>\n<html></html>", document.to_string());
+}
+
+#[test]
+fn _0090() {
+  let mut element = HtmlElement::new("ol");
+  element.append_children(["1", "2", "3"].into_iter().map(|text| HtmlElement::new("li").with_text(text)));
+  let old = element.replace_child(1, HtmlElement::new("li").with_text("two")).unwrap();
+  assert_eq!(HtmlElement::new("li").with_text("2"), old);
+  assert!(element.replace_child(3, HtmlElement::new("li")).is_none());
+  let mut buffer = String::new();
+  element.write(0, &mut buffer);
+  assert_eq!("<ol>\n  <li>1</li>\n  <li>two</li>\n  <li>3</li>\n</ol>", buffer);
+}
+
+#[test]
+#[cfg(feature = "highlight")]
+fn _0091() {
+  let element = HtmlElement::new("p").with_attr("class", "note").with_text("Hi");
+  assert_eq!(
+    "<\x1b[34mp\x1b[0m \x1b[36mclass\x1b[0m=\"\x1b[32mnote\x1b[0m\">\x1b[33mHi\x1b[0m</\x1b[34mp\x1b[0m>",
+    element.to_highlighted_string()
+  );
+}
+
+#[test]
+fn _0092() {
+  let element = HtmlElement::void("input").with_attr("type", "text").with_attr("class", "field").with_attr("name", "q").with_attr("id", "search");
+  let write = |attribute_order| {
+    let mut buffer = String::new();
+    element.write_with(0, &SerializeOptions { attribute_order, ..Default::default() }, &mut buffer);
+    buffer
+  };
+  assert_eq!(r#"<input type="text" class="field" name="q" id="search">"#, write(AttributeOrder::Insertion));
+  assert_eq!(r#"<input class="field" id="search" name="q" type="text">"#, write(AttributeOrder::Alphabetical));
+  assert_eq!(
+    r#"<input id="search" class="field" name="q" type="text">"#,
+    write(AttributeOrder::Priority(vec!["id".to_string(), "class".to_string()]))
+  );
+}
+
+#[test]
+fn _0093() {
+  let element = HtmlElement::new("p")
+    .with_child(HtmlElement::new("b").with_text("Sale"))
+    .with_child(HtmlElement::text("Price: <only>"))
+    .with_child(HtmlElement::new_span(Some("price")).with_text("10 €"));
+  let mut buffer = String::new();
+  element.write(0, &mut buffer);
+  assert_eq!(
+    r#"<p>
+  <b>Sale</b>
+  Price: &lt;only&gt;
+  <span class="price">10 €</span>
+</p>"#,
+    buffer
+  );
+}
+
+#[test]
+fn _0094() {
+  let element = HtmlElement::new("body").with_attr("bgcolor", "white").with_child(HtmlElement::new("center").with_text("Title"));
+  let issues = element.validate();
+  assert_eq!(2, issues.len());
+  assert!(issues.iter().all(|issue| issue.severity() == Severity::Warning));
+  assert_eq!("warning: <body> obsolete presentational attribute 'bgcolor'", issues[0].to_string());
+  assert_eq!("warning: <center> obsolete element", issues[1].to_string());
+  assert!(HtmlDocument::try_new("Title", "en", &[], element).is_ok());
+}
+
+#[test]
+fn _0095() {
+  let mut element = HtmlElement::void("img");
+  element.set_srcset(&[("photo.jpg", "1x"), ("photo large,hd.jpg", "2x"), ("fallback.jpg", "")]);
+  let mut buffer = String::new();
+  element.write(0, &mut buffer);
+  assert_eq!(r#"<img srcset="photo.jpg 1x, photo%20large%2Chd.jpg 2x, fallback.jpg">"#, buffer);
+}
+
+#[test]
+fn _0096() {
+  let element = HtmlElement::new_div(None)
+    .with_child(HtmlElement::new("ul").with_child(HtmlElement::new("li").with_text("One")).with_child(HtmlElement::new("li").with_text("Two")))
+    .with_child(HtmlElement::new_paragraph("First"))
+    .with_child(HtmlElement::new_paragraph("Second"))
+    .with_child(HtmlElement::new_span(None).with_text("Last"));
+  let mut buffer = String::new();
+  element.write_with(0, &SerializeOptions { omit_optional_end_tags: true, ..Default::default() }, &mut buffer);
+  assert_eq!(
+    r#"<div>
+  <ul>
+    <li>One
+    <li>Two
+  </ul>
+  <p>First
+  <p>Second</p>
+  <span>Last</span>
+</div>"#,
+    buffer
+  );
+}
+
+#[test]
+fn _0097() {
+  let mut element = HtmlElement::new("button").with_text("Save");
+  element.set_event("click", r#"save("draft") && alert('saved')"#).unwrap();
+  let mut buffer = String::new();
+  element.write(0, &mut buffer);
+  assert_eq!(r#"<button onclick="save(&quot;draft&quot;) &amp;&amp; alert(&#39;saved&#39;)">Save</button>"#, buffer);
+}
+
+#[test]
+fn _0098() {
+  let head = HtmlElement::new("head").with_child(HtmlElement::new("title").with_text("Title"));
+  let mut document = HtmlDocument::with_head_body("en", head, HtmlElement::new("body"));
+  document.add_resource_hint("preload", "/fonts/Barlow.woff2", Some("font"));
+  document.add_resource_hint("preconnect", "https://cdn.example.com", None);
+  assert_eq!(
+    r#"<!DOCTYPE html>
+<html lang="en" xmlns="http://www.w3.org/1999/xhtml">
+  <head>
+    <title>Title</title>
+    <link rel="preload" href="/fonts/Barlow.woff2" as="font" crossorigin>
+    <link rel="preconnect" href="https://cdn.example.com">
+  </head>
+  <body></body>
+</html>"#,
+    document.to_string()
+  );
+}
+
+#[test]
+fn _0099() {
+  let mut paragraph = HtmlElement::new("p");
+  paragraph.set_content("Line 1\nLine 2");
+  let element = HtmlElement::new_div(None).with_child(HtmlElement::new("ul").with_child(HtmlElement::new("li").with_text("One"))).with_child(paragraph);
+  let mut buffer = String::new();
+  element.write_with(0, &SerializeOptions::tabs(), &mut buffer);
+  assert_eq!("<div>\n\t<ul>\n\t\t<li>One</li>\n\t</ul>\n\t<p>\n\t\tLine 1\n\t\tLine 2\n\t</p>\n</div>", buffer);
+}
+
+#[test]
+fn _0100() {
+  let mut element = HtmlElement::new("p");
+  element.set_content_template("Hello {name} & {unknown}, you have {count} <new> messages {", &[("name", "<World>"), ("count", "3")]);
+  let mut buffer = String::new();
+  element.write(0, &mut buffer);
+  assert_eq!("<p>Hello &lt;World&gt; &amp; {unknown}, you have 3 &lt;new&gt; messages {</p>", buffer);
+}
+
+#[test]
+fn _0101() {
+  let element = HtmlElement::new_div(None).with_attr("data-a", "0123456789").with_attr("data-b", "01234&amp;56").with_attr("data-c", "short");
+  let mut buffer = String::new();
+  element.write_with(0, &SerializeOptions { max_attr_len: Some(8), ..Default::default() }, &mut buffer);
+  assert_eq!(r#"<div data-a="01234567" data-b="01234" data-c="short"></div>"#, buffer);
+}
+
+#[test]
+fn _0102() {
+  let spec = [("a", "/home", "Home"), ("a", "/about", "About")];
+  let links = spec
+    .iter()
+    .map(|(name, href, text)| HtmlElement::from_parts(name, vec![("href".to_string(), href.to_string())], vec![HtmlElement::text(text)]))
+    .collect();
+  let element = HtmlElement::from_parts("nav", vec![("class".to_string(), "menu".to_string())], links);
+  let mut buffer = String::new();
+  element.write(0, &mut buffer);
+  assert_eq!(
+    r#"<nav class="menu">
+  <a href="/home">Home</a>
+  <a href="/about">About</a>
+</nav>"#,
+    buffer
+  );
+}
+
+#[test]
+fn _0103() {
+  let mut paragraph = HtmlElement::new("p")
+    .with_child(HtmlElement::text("text "))
+    .with_child(HtmlElement::new("b").with_text("bold"))
+    .with_child(HtmlElement::text(" more "))
+    .with_child(HtmlElement::new_span(None).with_child(HtmlElement::new("code").with_text("x < y")).with_child(HtmlElement::text("!")));
+  paragraph.set_inline(true);
+  let element = HtmlElement::new_div(None).with_child(paragraph);
+  let mut buffer = String::new();
+  element.write(0, &mut buffer);
+  assert_eq!("<div>\n  <p>text <b>bold</b> more <span><code>x &lt; y</code>!</span></p>\n</div>", buffer);
+  let mut link = HtmlElement::new("a").with_child(HtmlElement::new_div(None));
+  link.set_inline(false);
+  let mut buffer = String::new();
+  link.write(0, &mut buffer);
+  assert_eq!("<a>\n  <div></div>\n</a>", buffer);
+}
+
+#[test]
+fn _0104() {
+  let trees = [
+    HtmlElement::void("br"),
+    HtmlElement::new_paragraph("Zażółć gęślą jaźń"),
+    HtmlElement::new_breadcrumb(&[("Home", Some("/")), ("Page", None)]),
+    HtmlElement::new_picture(&[("(min-width: 800px)", "large.webp")], "small.jpg", "Alt"),
+  ];
+  for tree in &trees {
+    assert_eq!(tree.to_string().len(), tree.serialized_len());
+    let opts = SerializeOptions { minify: true, ..Default::default() };
+    let mut buffer = String::new();
+    tree.write_with(0, &opts, &mut buffer);
+    assert_eq!(buffer.len(), tree.serialized_len_with(&opts));
+  }
+}
+
+#[test]
+fn _0105() {
+  let element = HtmlElement::new_paragraph("Fish & <chips>").with_attr("title", "Fish &amp; chips");
+  let write = |entity_style| {
+    let mut buffer = String::new();
+    element.write_with(0, &SerializeOptions { entity_style, ..Default::default() }, &mut buffer);
+    buffer
+  };
+  assert_eq!(r#"<p title="Fish &amp; chips">Fish &amp; &lt;chips&gt;</p>"#, write(EntityStyle::Named));
+  assert_eq!(r#"<p title="Fish &#38; chips">Fish &#38; &#60;chips&#62;</p>"#, write(EntityStyle::Numeric));
+}
+
+#[test]
+fn _0106() {
+  let document = HtmlDocument::new_with_media_styles("Title", "en", &[(None, "body { margin: 0; }"), (Some("print"), "nav { display: none; }")], HtmlElement::new("body"));
+  let output = document.to_string();
+  assert!(output.contains("    <style>body { margin: 0; }</style>\n    <style media=\"print\">nav { display: none; }</style>\n  </head>"));
+}
+
+#[test]
+fn _0107() {
+  let list = HtmlElement::new("ul").with_child(HtmlElement::new("li").with_text("One & two"));
+  let mut element = HtmlElement::new_div(None);
+  element.set_inner(&list);
+  let mut buffer = String::new();
+  element.write(0, &mut buffer);
+  assert_eq!("<div>\n  <ul>\n    <li>One &amp; two</li>\n  </ul>\n</div>", buffer);
+  let mut element = HtmlElement::new_paragraph("");
+  element.set_inner(&HtmlElement::new("b").with_text("bold"));
+  assert_eq!("<p><b>bold</b></p>", element.to_string());
+}
+
+#[test]
+fn _0108() {
+  let element = HtmlElement::new("body")
+    .with_child(HtmlElement::new("header").with_child(HtmlElement::new("h1")))
+    .with_child(HtmlElement::new("main").with_child(HtmlElement::new("section").with_child(HtmlElement::new_paragraph("Text"))))
+    .with_child(HtmlElement::new("footer"));
+  let names: Vec<&str> = element.descendants().map(|e| e.name()).collect();
+  assert_eq!(vec!["header", "h1", "main", "section", "p", "footer"], names);
+  assert_eq!(0, HtmlElement::new("br").descendants().count());
+}
+
+#[test]
+fn _0109() {
+  let integrity = "sha384-oqVuAfXRKap7fdgcCY5uykM6+R9GqQ8K/uxy9rx7HNQlGYl1kPzQho1wx4JwY8wC";
+  let element = HtmlElement::new_stylesheet("https://cdn.example.com/style.css", Some(integrity), Some("anonymous"));
+  assert_eq!(
+    format!(r#"<link rel="stylesheet" href="https://cdn.example.com/style.css" integrity="{}" crossorigin="anonymous">"#, integrity),
+    element.to_string()
+  );
+  let element = HtmlElement::new_script("/js/app.js", None, None);
+  assert_eq!(r#"<script src="/js/app.js"></script>"#, element.to_string());
+}
+
+#[test]
+fn _0110() {
+  let element = HtmlElement::new_noscript(HtmlElement::new_paragraph("Please enable <JavaScript>."));
+  assert_eq!("<noscript>\n  <p>Please enable &lt;JavaScript&gt;.</p>\n</noscript>", element.to_string());
+}
+
+#[test]
+fn _0111() {
+  let mut element = HtmlElement::new("main")
+    .with_child(HtmlElement::new_div(Some("ad")))
+    .with_child(HtmlElement::new_paragraph("Text"))
+    .with_child(HtmlElement::new_div(Some("ad banner")))
+    .with_child(HtmlElement::new_div(None));
+  element.retain_children(|child| !child.has_class("ad"));
+  assert_eq!("<main>\n  <p>Text</p>\n  <div></div>\n</main>", element.to_string());
+  element.retain_children(|child| !child.is_empty());
+  assert_eq!("<main>\n  <p>Text</p>\n</main>", element.to_string());
+}
+
+#[test]
+fn _0112() {
+  let mut element = HtmlElement::new_div(None);
+  element.set_tabindex(0);
+  element.set_tabindex(-1);
+  assert_eq!(r#"<div tabindex="-1"></div>"#, element.to_string());
+}
+
+#[test]
+fn _0113() {
+  let element = HtmlElement::new_labeled_input("user-email", "E-mail <required>", "email", "email");
+  assert_eq!(
+    r#"<div>
+  <label for="user-email">E-mail &lt;required&gt;</label>
+  <input type="email" id="user-email" name="email">
+</div>"#,
+    element.to_string()
+  );
+}
+
+#[test]
+fn _0114() {
+  let head = HtmlElement::new("head").with_child(HtmlElement::new("title").with_text("Title"));
+  let mut document = HtmlDocument::with_head_body("en", head, HtmlElement::new("body").with_child(HtmlElement::new_main()));
+  let before = document.to_string();
+  document.body_mut().unwrap().add_child(HtmlElement::new_footer().with_text("Footer"));
+  document.root_mut().set_attr("data-theme", "dark");
+  assert_eq!(
+    r#"<!DOCTYPE html>
+<html lang="en" xmlns="http://www.w3.org/1999/xhtml" data-theme="dark">
+  <head>
+    <title>Title</title>
+  </head>
+  <body>
+    <main></main>
+    <footer>Footer</footer>
+  </body>
+</html>"#,
+    document.to_string()
+  );
+  assert_ne!(before, document.to_string());
+  assert!(HtmlDocument::from_root(HtmlElement::new("definitions"), false).body_mut().is_none());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn _0115() {
+  let element = HtmlElement::new("ul").with_child(HtmlElement::new("li").with_text("One")).with_child(HtmlElement::new("li").with_text("Two"));
+  let mut pretty = vec![];
+  element.to_writer_pretty(&mut pretty).unwrap();
+  let mut minified = vec![];
+  element.to_writer_minified(&mut minified).unwrap();
+  assert_eq!(element.to_string().as_bytes(), pretty.as_slice());
+  assert_eq!(b"<ul><li>One</li><li>Two</li></ul>", minified.as_slice());
+  assert!(minified.len() < pretty.len());
+  let document = HtmlDocument::from_root(element, true);
+  let mut minified = vec![];
+  document.to_writer_minified(&mut minified).unwrap();
+  assert_eq!(b"<!DOCTYPE html><ul><li>One</li><li>Two</li></ul>", minified.as_slice());
+  let mut writer = FailingWriter { remaining: 5, written: vec![] };
+  assert!(document.to_writer_pretty(&mut writer).is_err());
+}
+
+#[test]
+fn _0116() {
+  let mut element = HtmlElement::new("button").with_class("btn");
+  element.set_classes_conditional(&[("active", true), ("disabled", false), ("btn", true), ("large primary", true)]);
+  assert_eq!(r#"<button class="btn active large primary"></button>"#, element.to_string());
+  let mut element = HtmlElement::new("button");
+  element.set_classes_conditional(&[("hidden", false)]);
+  assert!(!element.has_attr("class"));
+}
+
+#[test]
+fn _0117() {
+  let element = HtmlElement::new_media(MediaKind::Video, &[("movie.webm", "video/webm"), ("movie.mp4", "video/mp4")], true, "Your browser does not support <video>.");
+  assert_eq!(
+    r#"<video controls>
+  <source src="movie.webm" type="video/webm">
+  <source src="movie.mp4" type="video/mp4">
+  Your browser does not support &lt;video&gt;.
+</video>"#,
+    element.to_string()
+  );
+}
+
+#[test]
+fn _0118() {
+  let tree = |text: &str| HtmlElement::new("body").with_child(HtmlElement::new_paragraph(text));
+  assert_eq!(tree("Hello").content_hash(), tree("Hello").content_hash());
+  assert_ne!(tree("Hello").content_hash(), tree("Hallo").content_hash());
+  assert_eq!(0xcbf2_9ce4_8422_2325, HtmlElement::text("").content_hash());
+  assert_eq!(0xaf63_dc4c_8601_ec8c, HtmlElement::text("a").content_hash());
+  let document = HtmlDocument::from_root(tree("Hello"), true);
+  assert_eq!(document.content_hash(), HtmlDocument::from_root(tree("Hello"), true).content_hash());
+  assert_ne!(document.content_hash(), tree("Hello").content_hash());
+}
+
+#[test]
+fn _0119() {
+  let head = HtmlElement::new("head");
+  let mut document = HtmlDocument::with_head_body("en", head, HtmlElement::new("body"));
+  document.set_root_attr("class", "light");
+  document.set_root_attr("prefix", "og: https://ogp.me/ns#");
+  document.set_root_attr("class", "dark");
+  assert!(document
+    .to_string()
+    .contains(r#"<html lang="en" xmlns="http://www.w3.org/1999/xhtml" prefix="og: https://ogp.me/ns#" class="dark">"#));
+}
+
+#[test]
+#[cfg(feature = "encoding")]
+fn _0120() {
+  let head = HtmlElement::new("head").with_child(HtmlElement::void("meta").with_attr("charset", "UTF-8"));
+  let document = HtmlDocument::with_head_body("pl", head, HtmlElement::new("body").with_child(HtmlElement::new_paragraph("Café żółw")));
+  let bytes = document.to_encoded_bytes(encoding_rs::WINDOWS_1252);
+  let expected = b"<!DOCTYPE html>
+<html lang=\"pl\" xmlns=\"http://www.w3.org/1999/xhtml\">
+  <head>
+    <meta charset=\"windows-1252\">
+  </head>
+  <body>
+    <p>Caf\xe9 &#380;\xf3&#322;w</p>
+  </body>
+</html>";
+  assert_eq!(expected.as_slice(), bytes.as_slice());
+}
+
+#[test]
+fn _0121() {
+  let logo = || HtmlElement::void("img").with_attr("src", "logo.png").with_attr("alt", "Logo");
+  assert_eq!(r#"<a href="/home"><img src="logo.png" alt="Logo"></a>"#, HtmlElement::maybe_linked(Some("/home"), logo()).to_string());
+  assert_eq!(r#"<img src="logo.png" alt="Logo">"#, HtmlElement::maybe_linked(None, logo()).to_string());
+}
+
+#[test]
+fn _0122() {
+  let element = HtmlElement::new_table(
+    &[ColSpec::width("30%").with_class("name"), ColSpec::width("70%")],
+    &["Name", "Description"],
+    &[&["a", "First & best"], &["b", "Second"]],
+  );
+  assert_eq!(
+    r#"<table>
+  <colgroup>
+    <col style="width: 30%" class="name">
+    <col style="width: 70%">
+  </colgroup>
+  <thead>
+    <tr>
+      <th>Name</th>
+      <th>Description</th>
+    </tr>
+  </thead>
+  <tbody>
+    <tr>
+      <td>a</td>
+      <td>First &amp; best</td>
+    </tr>
+    <tr>
+      <td>b</td>
+      <td>Second</td>
+    </tr>
+  </tbody>
+</table>"#,
+    element.to_string()
+  );
+  assert_eq!("<table>\n  <tbody></tbody>\n</table>", HtmlElement::new_table(&[], &[], &[]).to_string());
+}
+
+#[test]
+fn _0123() {
+  let mut element = HtmlElement::new("button");
+  let issue = element.set_event(r#"click" onmouseover="alert(1)"#, "save()").unwrap_err();
+  assert_eq!(r#"error: <button> has invalid event name 'click" onmouseover="alert(1)'"#, issue.to_string());
+  assert!(element.set_event("", "save()").is_err());
+  assert!(element.set_event("Click", "save()").is_err());
+  assert_eq!("<button></button>", element.to_string());
+}
+
+#[test]
+#[cfg(feature = "encoding")]
+fn _0124() {
+  let head = HtmlElement::new("head").with_child(HtmlElement::void("meta").with_attr("charset", "UTF-8"));
+  let document = HtmlDocument::with_head_body("pl", head, HtmlElement::new("body").with_child(HtmlElement::new_paragraph("żółw")));
+  for encoding in [encoding_rs::UTF_16LE, encoding_rs::UTF_16BE] {
+    let bytes = document.to_encoded_bytes(encoding);
+    assert_eq!(document.to_string().as_bytes(), bytes.as_slice());
+    assert!(String::from_utf8(bytes).unwrap().contains(r#"<meta charset="UTF-8">"#));
+  }
+}
+
+#[test]
+fn _0125() {
+  let element = HtmlElement::new("body")
+    .with_child(HtmlElement::new("script").with_raw("let s = 'é' && t;"))
+    .with_child(HtmlElement::new("style").with_child(HtmlElement::text("p::after { content: '→'; }")))
+    .with_child(HtmlElement::new_paragraph("é & è"));
+  let opts = SerializeOptions {
+    ascii_only: true,
+    entity_style: EntityStyle::Numeric,
+    ..Default::default()
+  };
+  let mut buffer = String::new();
+  element.write_with(0, &opts, &mut buffer);
+  assert_eq!(
+    "<body>\n  <script>let s = 'é' && t;</script>\n  <style>\n    p::after { content: '→'; }\n  </style>\n  <p>&#233; &#38; &#232;</p>\n</body>",
+    buffer
+  );
+}
+
+#[test]
+fn _0126() {
+  let element = HtmlElement::new_media(MediaKind::Audio, &[("song.ogg", "audio/ogg")], false, "");
+  assert_eq!("<audio>\n  <source src=\"song.ogg\" type=\"audio/ogg\">\n</audio>", element.to_string());
+}
+
+#[test]
+fn _0127() {
+  let element = HtmlElement::new("a").with_text("Home ").with_child(HtmlElement::new("b").with_text("x"));
+  assert_eq!("Home <b>x</b>", element.inner_html());
+  let element = HtmlElement::new("div").with_text("Home").with_child(HtmlElement::new("b").with_text("x"));
+  assert_eq!("Home\n<b>x</b>", element.inner_html());
+}
+
+#[test]
+fn _0128() {
+  let text = "The quick brown fox jumps over the lazy dog and keeps running far away.";
+  let element = HtmlElement::new_paragraph(text);
+  let opts = SerializeOptions {
+    max_width: Some(40),
+    minify: true,
+    ..Default::default()
+  };
+  let mut buffer = String::new();
+  element.write_with(0, &opts, &mut buffer);
+  assert_eq!(format!("<p>{}</p>", text), buffer);
+  let opts = SerializeOptions {
+    max_width: Some(40),
+    block_content_lines: Some(3),
+    ..Default::default()
+  };
+  let mut buffer = String::new();
+  element.write_with(0, &opts, &mut buffer);
+  assert_eq!(format!("<p>{}</p>", text), buffer);
+}
+
+#[test]
+fn _0129() {
+  let element = HtmlElement::new_div(None).with_attr("title", r#"say "hi" 'x'"#);
+  let mut buffer = String::new();
+  element.write_with(0, &SerializeOptions { max_attr_len: Some(8), ..Default::default() }, &mut buffer);
+  assert_eq!(r#"<div title="say "></div>"#, buffer);
+  let mut buffer = String::new();
+  element.write_with(0, &SerializeOptions { max_attr_len: Some(10), ..Default::default() }, &mut buffer);
+  assert_eq!(r#"<div title="say &quot;"></div>"#, buffer);
+}
+
+#[test]
+fn _0130() {
+  let object = HtmlElement::new("foreignObject")
+    .with_attr("requiredExtensions", "html")
+    .with_child(HtmlElement::new("div").with_attr("onClick", "go()"));
+  let element = HtmlElement::new("svg").with_attr("viewBox", "0 0 10 10").with_child(object);
+  let opts = SerializeOptions {
+    lowercase_attr_names: true,
+    ..Default::default()
+  };
+  let mut buffer = String::new();
+  element.write_with(0, &opts, &mut buffer);
+  assert_eq!(
+    r#"<svg viewBox="0 0 10 10">
+  <foreignObject requiredExtensions="html">
+    <div onclick="go()"></div>
+  </foreignObject>
+</svg>"#,
+    buffer
+  );
+}
+
+#[test]
+fn _0131() {
+  let mut element = HtmlElement::new("p");
+  let breaks = HtmlElement::text_with_breaks("\nfirst\n\nsecond\n");
+  assert_eq!(6, breaks.len());
+  element.add_children(breaks);
+  assert_eq!(
+    r#"<p>
+  <br>
+  first
+  <br>
+  <br>
+  second
+  <br>
+</p>"#,
+    format!("{}", element)
+  );
+  assert!(HtmlElement::text_with_breaks("").is_empty());
+}
+
+#[test]
+fn _0132() {
+  let element = HtmlElement::new_picture(&[], "my image é.jpg", "Alt");
+  assert_eq!(
+    "<picture>\n  <img src=\"my%20image%20%C3%A9.jpg\" alt=\"Alt\">\n</picture>",
+    element.to_string()
+  );
+}
+
+#[test]
+#[cfg(feature = "encoding")]
+fn _0133() {
+  let head = HtmlElement::new("head")
+    .with_child(HtmlElement::void("meta").with_attr("charset", "UTF-8"))
+    .with_child(HtmlElement::new("style").with_raw("p::after { content: 'ż'; }"));
+  let body = HtmlElement::new("body")
+    .with_child(HtmlElement::new("script").with_raw("alert('ż😀é');"))
+    .with_child(HtmlElement::new_paragraph("ż"));
+  let document = HtmlDocument::with_head_body("pl", head, body);
+  let bytes = document.to_encoded_bytes(encoding_rs::WINDOWS_1252);
+  let (text, _, _) = encoding_rs::WINDOWS_1252.decode(&bytes);
+  assert!(text.contains(r"<style>p::after { content: '\17C '; }</style>"));
+  assert!(text.contains(r"<script>alert('\u017C\uD83D\uDE00é');</script>"));
+  assert!(text.contains("<p>&#380;</p>"));
+}
+
+#[test]
+fn _0134() {
+  let mut document = HtmlDocument::new_with_media_styles("Title", "en", &[(Some("print"), "body{}"), (None, "")], HtmlElement::new("body"));
+  document.add_css("");
+  document.add_css("p{color:red}");
+  let output = document.to_string();
+  assert!(output.contains("    <style media=\"print\">body{}</style>\n    <style>p{color:red}</style>\n  </head>"));
+  assert_eq!(2, output.matches("<style").count());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn _0135() {
+  let mut row = HtmlElement::new("tr");
+  row.add_child(HtmlElement::new("td").with_text("cell"));
+  let mut buffer = String::new();
+  row.write_cached(0, &mut buffer);
+  let mut table = HtmlElement::new("table");
+  table.add_child(row);
+  table.add_child(HtmlElement::new("tr").with_child(HtmlElement::new("td").with_text("other")));
+  let mut buffer = String::new();
+  table.write_cached(0, &mut buffer);
+  assert_eq!(format!("{}", table), buffer);
+  // the row moved to a new depth is cached again for the new indentation
+  assert!(table.child_mut(0).unwrap().is_cached(2));
+  assert!(!table.child_mut(0).unwrap().is_cached(0));
+  // only the edited cell and its ancestors are serialized again
+  table.child_mut(1).and_then(|tr| tr.child_mut(0)).unwrap().set_class("selected");
+  assert!(!table.is_cached(0));
+  assert!(!table.child_mut(1).unwrap().is_cached(2));
+  assert!(table.child_mut(0).unwrap().is_cached(2));
+  let mut buffer = String::new();
+  table.write_cached(0, &mut buffer);
+  assert!(buffer.contains(r#"<td class="selected">other</td>"#));
+  assert_eq!(format!("{}", table), buffer);
+}
+
+#[test]
+fn _0136() {
+  let mut element = HtmlElement::new("td");
+  element.set_attr("colspan", 2);
+  element.set_attr("tabindex", "0");
+  let mut other = HtmlElement::new("td");
+  other.set_tabindex(0);
+  other.set_attr_int("colspan", 2);
+  assert!(element.diff(&other).is_empty());
+  assert!(element.eq_ignoring_attr_order(&other));
+  let mut other = HtmlElement::new("td");
+  other.set_tabindex(0);
+  other.set_attr_int("colspan", 3);
+  assert_eq!(1, element.diff(&other).len());
+  assert!(!element.eq_ignoring_attr_order(&other));
+}
+
+#[test]
+fn _0137() {
+  let element = HtmlElement::new_div(None).with_attr("data-x", "Tom & Jerry").with_attr("data-y", "Tom &amp; Jerry").with_attr("data-z", "a&b;c");
+  let mut buffer = String::new();
+  element.write_with(0, &SerializeOptions { max_attr_len: Some(7), ..Default::default() }, &mut buffer);
+  assert_eq!(r#"<div data-x="Tom & J" data-y="Tom " data-z="a&b;c"></div>"#, buffer);
+  let mut buffer = String::new();
+  element.write_with(0, &SerializeOptions { max_attr_len: Some(3), ..Default::default() }, &mut buffer);
+  assert_eq!(r#"<div data-x="Tom" data-y="Tom" data-z="a"></div>"#, buffer);
+}
+
+#[test]
+fn _0138() {
+  let script = HtmlElement::new("script").with_raw("a='</script>'");
+  assert_eq!(r#"<script>a='<\/script>'</script>"#, script.to_string());
+  assert_eq!(r#"a='<\/script>'"#, script.inner_html());
+  let style = HtmlElement::new("style").with_raw("p {}\n/* </STYLE> */");
+  assert_eq!("p {}\n/* <\\/STYLE> */", style.inner_html());
+}
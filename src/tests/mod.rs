@@ -12,4 +12,6 @@
  * SOFTWARE.
  */
 
-mod html_builder;
\ No newline at end of file
+mod escape;
+mod form_builder;
+mod html_builder;
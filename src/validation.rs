@@ -0,0 +1,113 @@
+/*
+ * MIT license
+ *
+ * Copyright (c) 2023 Dariusz Depta
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Issues reported when validating `HTML` elements.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Severity of the validation issue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+  /// Issue making the serialized output incorrect.
+  Error,
+  /// Issue not affecting the serialized output, but worth reporting.
+  Warning,
+}
+
+/// Issue reported when validating `HTML` elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+  severity: Severity,
+  element: String,
+  message: String,
+}
+
+impl ValidationIssue {
+  /// Creates a new issue with error severity.
+  pub(crate) fn error(element: &str, message: &str) -> Self {
+    Self {
+      severity: Severity::Error,
+      element: element.to_string(),
+      message: message.to_string(),
+    }
+  }
+
+  /// Creates a new issue with warning severity.
+  pub(crate) fn warning(element: &str, message: &str) -> Self {
+    Self {
+      severity: Severity::Warning,
+      element: element.to_string(),
+      message: message.to_string(),
+    }
+  }
+
+  /// Returns the severity of the issue.
+  pub fn severity(&self) -> Severity {
+    self.severity
+  }
+
+  /// Returns the name of the element the issue was reported for.
+  pub fn element(&self) -> &str {
+    &self.element
+  }
+
+  /// Returns the description of the issue.
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+}
+
+impl fmt::Display for ValidationIssue {
+  /// Converts the validation issue into text.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let severity = match self.severity {
+      Severity::Error => "error",
+      Severity::Warning => "warning",
+    };
+    write!(f, "{}: <{}> {}", severity, self.element, self.message)
+  }
+}
+
+/// Error reported when building a document fails, aggregating all found issues.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildError {
+  issues: Vec<ValidationIssue>,
+}
+
+impl BuildError {
+  /// Creates a new build error from specified issues.
+  pub(crate) fn new(issues: Vec<ValidationIssue>) -> Self {
+    Self { issues }
+  }
+
+  /// Returns all issues that caused the build error.
+  pub fn issues(&self) -> &[ValidationIssue] {
+    &self.issues
+  }
+}
+
+impl fmt::Display for BuildError {
+  /// Converts the build error into text, listing all issues.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "building document failed")?;
+    for issue in &self.issues {
+      write!(f, "\n  {}", issue)?;
+    }
+    Ok(())
+  }
+}
+
+impl core::error::Error for BuildError {}